@@ -1,20 +1,136 @@
 use std::collections::BTreeMap;
 
-use serde::Serialize;
+use serde::de::Error;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::util::units::Second;
+use crate::benchmark::calibration::Calibration;
+use crate::options::{OutlierPolicy, SummaryStatistic};
+use crate::outlier_detection::{remove_outliers, winsorize};
+use crate::util::percentile::percentile;
+use crate::util::units::{Scalar, Second};
+
+/// The kind of a run of a benchmarked command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum RunKind {
+    Warmup,
+    Benchmark,
+}
+
+/// Details about a single run of a benchmarked command
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(schemars::JsonSchema))]
+pub struct Run {
+    /// Whether this is a warmup or a benchmark run
+    pub kind: RunKind,
+
+    /// Index of the run among the runs of the same kind (like `$HYPERFINE_ITERATION`)
+    pub iteration: u64,
+
+    /// Start of the run, relative to the start of the benchmark session
+    pub start: Second,
+}
+
+/// Wall clock times of everything that is executed around the benchmark runs
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(schemars::JsonSchema))]
+pub struct PhaseTimes {
+    /// Times of the warmup runs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warmup: Vec<Second>,
+
+    /// Times of the '--prepare' command, once before every warmup and benchmark run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prepare: Vec<Second>,
+
+    /// Times of the '--conclude' command, once after every warmup and benchmark run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conclude: Vec<Second>,
+
+    /// Time of the '--setup' command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub setup: Option<Second>,
+
+    /// Time of the '--cleanup' command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cleanup: Option<Second>,
+}
+
+/// Percentiles of the run times, as pairs of `p` (between 0 and 100) and the respective value.
+/// Serialized as a map with keys like `p50`, `p99.9`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Percentiles(pub Vec<(Scalar, Second)>);
+
+impl Percentiles {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Look up the value for the `p`-th percentile, if it has been computed
+    pub fn get(&self, p: Scalar) -> Option<Second> {
+        self.0
+            .iter()
+            .find(|(q, _)| *q == p)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Scalar, Second)> {
+        self.0.iter()
+    }
+
+    /// The name of the `p`-th percentile, as used in the exports
+    pub fn name(p: Scalar) -> String {
+        format!("p{p}")
+    }
+
+    /// Parse the name of a percentile like "p95" (the inverse of `Percentiles::name`)
+    pub fn parse_name(name: &str) -> Option<Scalar> {
+        name.strip_prefix('p')
+            .and_then(|p| p.parse::<Scalar>().ok())
+            .filter(|p| (0.0..=100.0).contains(p))
+    }
+}
+
+impl Serialize for Percentiles {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (p, value) in &self.0 {
+            map.serialize_entry(&Self::name(*p), value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Percentiles {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let map = BTreeMap::<String, Second>::deserialize(deserializer)?;
+        let mut percentiles = map
+            .into_iter()
+            .map(|(name, value)| {
+                Self::parse_name(&name)
+                    .map(|p| (p, value))
+                    .ok_or_else(|| D::Error::custom(format!("invalid percentile name '{name}'")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        percentiles.sort_by(|(p, _), (q, _)| p.total_cmp(q));
+        Ok(Percentiles(percentiles))
+    }
+}
 
 /// Set of values that will be exported.
 // NOTE: `serde` is used for JSON serialization, but not for CSV serialization due to the
 // `parameters` map. Update `src/hyperfine/export/csv.rs` with new fields, as appropriate.
-#[derive(Debug, Default, Clone, Serialize, PartialEq)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(test, derive(schemars::JsonSchema))]
 pub struct BenchmarkResult {
     /// The full command line of the program that is being benchmarked
     pub command: String,
 
     /// The full command line of the program that is being benchmarked, possibly including a list of
     /// parameters that were not used in the command line template.
-    #[serde(skip_serializing)]
+    #[serde(skip)]
     pub command_with_unused_parameters: String,
 
     /// The average run time
@@ -38,10 +154,33 @@ pub struct BenchmarkResult {
     /// Maximum of all measured times
     pub max: Second,
 
+    /// Selected percentiles of all measured times
+    #[serde(default, skip_serializing_if = "Percentiles::is_empty")]
+    #[cfg_attr(test, schemars(with = "BTreeMap<String, Second>"))]
+    pub percentiles: Percentiles,
+
     /// All run time measurements
     #[serde(skip_serializing_if = "Option::is_none")]
     pub times: Option<Vec<Second>>,
 
+    /// Time spent in user mode, for each run in `times`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub times_user: Option<Vec<Second>>,
+
+    /// Time spent in kernel mode, for each run in `times`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub times_system: Option<Vec<Second>>,
+
+    /// Indices of the runs (in `times`) that have been removed or clamped as statistical
+    /// outliers before computing the summary statistics
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outliers: Vec<usize>,
+
+    /// Whether the outliers have been removed or clamped (not present if all measurements
+    /// have been kept)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outlier_policy: Option<OutlierPolicy>,
+
     /// Maximum memory usage of the process, in bytes
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memory_usage_byte: Option<Vec<u64>>,
@@ -49,7 +188,74 @@ pub struct BenchmarkResult {
     /// Exit codes of all command invocations
     pub exit_codes: Vec<Option<i32>>,
 
+    /// Details about every run of the command, including warmup runs. The measurements of
+    /// the benchmark runs are part of `times`, `times_user`, `times_system` and `exit_codes`
+    /// (in the same order).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runs: Vec<Run>,
+
+    /// Times of the warmup runs and of the intermediate commands (only exported with
+    /// '--export-phase-times')
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase_times: Option<PhaseTimes>,
+
+    /// SHA-256 hash of the standard output of the command (only available if the output
+    /// has been checked)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_sha256: Option<String>,
+
+    /// Measurement of the shell spawning time that has been subtracted from the run times
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub calibration: Option<Calibration>,
+
     /// Parameter values for this benchmark
-    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub parameters: BTreeMap<String, String>,
 }
+
+impl BenchmarkResult {
+    /// The `p`-th percentile of the run times. If it has not been computed during the
+    /// benchmark, it is derived from the individual run times (if available), after
+    /// handling the outliers in the same way as for the other summary statistics.
+    pub fn percentile(&self, p: Scalar) -> Option<Second> {
+        self.percentiles.get(p).or_else(|| {
+            let times = self.times.as_ref().filter(|times| !times.is_empty())?;
+            let times = match self.outlier_policy {
+                Some(OutlierPolicy::Remove) => remove_outliers(times, &self.outliers),
+                Some(OutlierPolicy::Winsorize) => winsorize(times, &self.outliers),
+                _ => times.clone(),
+            };
+            (!times.is_empty()).then(|| percentile(&times, p))
+        })
+    }
+
+    /// The value of the given summary statistic for this benchmark
+    pub fn statistic(&self, statistic: SummaryStatistic) -> Second {
+        match statistic {
+            SummaryStatistic::Mean => self.mean,
+            SummaryStatistic::Median => self.median,
+            SummaryStatistic::Min => self.min,
+            SummaryStatistic::Percentile(p) => self.percentile(p).unwrap_or(self.median),
+        }
+    }
+}
+
+#[test]
+fn test_percentile_without_outliers() {
+    let mut result = BenchmarkResult {
+        percentiles: Percentiles(vec![(50.0, 2.0)]),
+        times: Some(vec![1.0, 2.0, 3.0, 100.0]),
+        outliers: vec![3],
+        outlier_policy: Some(OutlierPolicy::Remove),
+        ..Default::default()
+    };
+    assert_eq!(result.percentile(50.0), Some(2.0));
+    assert_eq!(result.percentile(100.0), Some(3.0));
+
+    result.outlier_policy = Some(OutlierPolicy::Winsorize);
+    assert_eq!(result.percentile(0.0), Some(1.0));
+    assert_eq!(result.percentile(100.0), Some(3.0));
+
+    result.outlier_policy = None;
+    assert_eq!(result.percentile(100.0), Some(100.0));
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use statistical::{mean, median, standard_deviation};
+
+use crate::outlier_detection::median_absolute_deviation;
+use crate::util::units::Second;
+
+/// Result of measuring the overhead of spawning a shell. The median is used as a robust
+/// estimate of the overhead, such that single slow calibration runs do not bias the results.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(schemars::JsonSchema))]
+pub struct Calibration {
+    /// Estimate of the wall clock time that is spent in the shell (median)
+    pub time_real: Second,
+
+    /// Estimate of the time spent in user mode (median)
+    pub time_user: Second,
+
+    /// Estimate of the time spent in kernel mode (median)
+    pub time_system: Second,
+
+    /// Mean wall clock time of the calibration runs
+    pub mean: Second,
+
+    /// Standard deviation of the wall clock time of the calibration runs
+    pub stddev: Second,
+
+    /// Standard error of the estimate `time_real` (based on the median absolute deviation)
+    pub uncertainty: Second,
+
+    /// Number of calibration runs
+    pub count: usize,
+}
+
+impl Calibration {
+    /// Compute the calibration from the individual run times (at least two runs are required)
+    pub fn from_runs(
+        times_real: &[Second],
+        times_user: &[Second],
+        times_system: &[Second],
+    ) -> Self {
+        assert!(times_real.len() >= 2);
+
+        let t_mean = mean(times_real);
+        let t_stddev = standard_deviation(times_real, Some(t_mean));
+        let count = times_real.len();
+
+        Calibration {
+            time_real: median(times_real),
+            time_user: median(times_user),
+            time_system: median(times_system),
+            mean: t_mean,
+            stddev: t_stddev,
+            // Asymptotic standard error of the median (for normally distributed samples). The
+            // standard deviation is estimated via the MAD, so that it is robust against outliers.
+            uncertainty: (std::f64::consts::PI / 2.0).sqrt()
+                * 1.4826
+                * median_absolute_deviation(times_real)
+                / (count as f64).sqrt(),
+            count,
+        }
+    }
+}
+
+#[test]
+fn test_calibration_is_robust_against_outliers() {
+    use approx::assert_relative_eq;
+
+    let mut times_real: Vec<Second> = (0..49)
+        .map(|i| if i % 2 == 0 { 0.0009 } else { 0.0011 })
+        .collect();
+    times_real.push(0.100);
+    let times_cpu = vec![0.0005; 50];
+
+    let calibration = Calibration::from_runs(&times_real, &times_cpu, &times_cpu);
+
+    assert_relative_eq!(calibration.time_real, 0.001);
+    assert_relative_eq!(calibration.time_user, 0.0005);
+    assert_relative_eq!(calibration.mean, 0.002978);
+    assert!(calibration.stddev > 0.01);
+    assert!(calibration.uncertainty > 0.0 && calibration.uncertainty < 0.0001);
+    assert_eq!(calibration.count, 50);
+}
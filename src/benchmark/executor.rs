@@ -1,20 +1,23 @@
+#[cfg(target_os = "linux")]
+use std::os::unix::process::CommandExt as _;
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
 use std::process::ExitStatus;
 
 use crate::command::Command;
 use crate::options::{
-    CmdFailureAction, CommandInputPolicy, CommandOutputPolicy, Options, OutputStyleOption, Shell,
+    CmdFailureAction, CommandInputPolicy, CommandOutputPolicy, Options, OutputStyleOption,
+    ProcessScheduling, Shell,
 };
 use crate::output::progress_bar::get_progress_bar;
 use crate::timer::{execute_and_measure, TimerResult};
 use crate::util::randomized_environment_offset;
 use crate::util::units::Second;
 
+use super::calibration::Calibration;
 use super::timing_result::TimingResult;
 
 use anyhow::{bail, Context, Result};
-use statistical::mean;
 
 pub enum BenchmarkIteration {
     NonBenchmarkRun,
@@ -53,30 +56,41 @@ pub trait Executor {
     /// that is being used in addition to the actual runtime
     /// of the command.
     fn time_overhead(&self) -> Second;
+
+    /// Return the result of the last calibration, if this
+    /// executor needs to be calibrated.
+    fn calibration(&self) -> Option<Calibration> {
+        None
+    }
 }
 
 fn run_command_and_measure_common(
-    mut command: std::process::Command,
+    mut process: std::process::Command,
     iteration: BenchmarkIteration,
     command_failure_action: CmdFailureAction,
     command_input_policy: &CommandInputPolicy,
     command_output_policy: &CommandOutputPolicy,
-    command_name: &str,
+    scheduling: &ProcessScheduling,
+    command: &Command<'_>,
 ) -> Result<TimerResult> {
-    let stdin = command_input_policy.get_stdin()?;
-    let (stdout, stderr) = command_output_policy.get_stdout_stderr()?;
-    command.stdin(stdin).stdout(stdout).stderr(stderr);
+    let command_name = command.get_command_line();
+    let (stdin, stdin_data) = command_input_policy.get_stdin(command, &iteration)?;
+    let (stdout, stderr) = command_output_policy.get_stdout_stderr(&iteration)?;
+    process.stdin(stdin).stdout(stdout).stderr(stderr);
 
-    command.env(
+    process.env(
         "HYPERFINE_RANDOMIZED_ENVIRONMENT_OFFSET",
         randomized_environment_offset::value(),
     );
 
     if let Some(value) = iteration.to_env_var_value() {
-        command.env("HYPERFINE_ITERATION", value);
+        process.env("HYPERFINE_ITERATION", value);
     }
 
-    let result = execute_and_measure(command)
+    apply_scheduling(&mut process, scheduling);
+
+    let digest_output = command_output_policy.digests_stdout();
+    let result = execute_and_measure(process, stdin_data, digest_output)
         .with_context(|| format!("Failed to run command '{command_name}'"))?;
 
     if !result.status.success() {
@@ -119,6 +133,56 @@ fn run_command_and_measure_common(
     Ok(result)
 }
 
+/// Set up the CPU affinity and the priority of the child process. This happens after
+/// forking, right before the command is executed, such that no additional process
+/// (like `taskset` or `chrt`) is part of the measurement.
+#[cfg(target_os = "linux")]
+fn apply_scheduling(command: &mut std::process::Command, scheduling: &ProcessScheduling) {
+    if *scheduling == ProcessScheduling::default() {
+        return;
+    }
+
+    // Prepare everything that needs allocations before forking
+    let cpu_set = scheduling.cpu_affinity.as_ref().map(|cpus| {
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        for &cpu in cpus {
+            unsafe { libc::CPU_SET(cpu, &mut set) };
+        }
+        set
+    });
+    let nice = scheduling.nice;
+    let sched_fifo = scheduling.sched_fifo;
+
+    // SAFETY: the closure only performs async-signal-safe system calls
+    unsafe {
+        command.pre_exec(move || {
+            if let Some(ref set) = cpu_set {
+                if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), set) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            if let Some(nice) = nice {
+                if libc::setpriority(libc::PRIO_PROCESS, 0, nice) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            if let Some(priority) = sched_fifo {
+                let param = libc::sched_param {
+                    sched_priority: priority,
+                };
+                if libc::sched_setscheduler(0, libc::SCHED_FIFO, &param) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Scheduling settings are rejected during option parsing on other platforms
+#[cfg(not(target_os = "linux"))]
+fn apply_scheduling(_command: &mut std::process::Command, _scheduling: &ProcessScheduling) {}
+
 pub struct RawExecutor<'a> {
     options: &'a Options,
 }
@@ -143,7 +207,8 @@ impl Executor for RawExecutor<'_> {
             command_failure_action.unwrap_or_else(|| self.options.command_failure_action.clone()),
             &self.options.command_input_policy,
             output_policy,
-            &command.get_command_line(),
+            &self.options.scheduling,
+            command,
         )?;
 
         Ok((
@@ -152,6 +217,7 @@ impl Executor for RawExecutor<'_> {
                 time_user: result.time_user,
                 time_system: result.time_system,
                 memory_usage_byte: result.memory_usage_byte,
+                output_digest: result.stdout_digest,
             },
             result.status,
         ))
@@ -169,7 +235,7 @@ impl Executor for RawExecutor<'_> {
 pub struct ShellExecutor<'a> {
     options: &'a Options,
     shell: &'a Shell,
-    shell_spawning_time: Option<TimingResult>,
+    calibration: Option<Calibration>,
 }
 
 impl<'a> ShellExecutor<'a> {
@@ -177,7 +243,7 @@ impl<'a> ShellExecutor<'a> {
         ShellExecutor {
             shell,
             options,
-            shell_spawning_time: None,
+            calibration: None,
         }
     }
 }
@@ -201,6 +267,7 @@ impl Executor for ShellExecutor<'_> {
         } else {
             command_builder.arg(command.get_command_line());
         }
+        command.configure_process(&mut command_builder)?;
 
         let mut result = run_command_and_measure_common(
             command_builder,
@@ -208,14 +275,16 @@ impl Executor for ShellExecutor<'_> {
             command_failure_action.unwrap_or_else(|| self.options.command_failure_action.clone()),
             &self.options.command_input_policy,
             output_policy,
-            &command.get_command_line(),
+            &self.options.scheduling,
+            command,
         )?;
 
-        // Subtract shell spawning time
-        if let Some(spawning_time) = self.shell_spawning_time {
-            result.time_real = (result.time_real - spawning_time.time_real).max(0.0);
-            result.time_user = (result.time_user - spawning_time.time_user).max(0.0);
-            result.time_system = (result.time_system - spawning_time.time_system).max(0.0);
+        // Subtract shell spawning time. The results are deliberately not clamped to zero, such
+        // that a biased calibration does not go unnoticed.
+        if let Some(calibration) = self.calibration {
+            result.time_real -= calibration.time_real;
+            result.time_user -= calibration.time_user;
+            result.time_system -= calibration.time_system;
         }
 
         Ok((
@@ -224,14 +293,19 @@ impl Executor for ShellExecutor<'_> {
                 time_user: result.time_user,
                 time_system: result.time_system,
                 memory_usage_byte: result.memory_usage_byte,
+                output_digest: result.stdout_digest,
             },
             result.status,
         ))
     }
 
-    /// Measure the average shell spawning time
+    /// Measure the shell spawning time
     fn calibrate(&mut self) -> Result<()> {
         const COUNT: u64 = 50;
+
+        // Measure the full time in case of a recalibration
+        self.calibration = None;
+
         let progress_bar = if self.options.output_style != OutputStyleOption::Disabled {
             Some(get_progress_bar(
                 COUNT,
@@ -284,18 +358,21 @@ impl Executor for ShellExecutor<'_> {
             bar.finish_and_clear()
         }
 
-        self.shell_spawning_time = Some(TimingResult {
-            time_real: mean(&times_real),
-            time_user: mean(&times_user),
-            time_system: mean(&times_system),
-            memory_usage_byte: 0,
-        });
+        self.calibration = Some(Calibration::from_runs(
+            &times_real,
+            &times_user,
+            &times_system,
+        ));
 
         Ok(())
     }
 
     fn time_overhead(&self) -> Second {
-        self.shell_spawning_time.unwrap().time_real
+        self.calibration.unwrap().time_real
+    }
+
+    fn calibration(&self) -> Option<Calibration> {
+        self.calibration
     }
 }
 
@@ -345,6 +422,7 @@ impl Executor for MockExecutor {
                 time_user: 0.0,
                 time_system: 0.0,
                 memory_usage_byte: 0,
+                output_digest: None,
             },
             status,
         ))
@@ -1,28 +1,41 @@
 pub mod benchmark_result;
+pub mod calibration;
 pub mod executor;
+pub mod output_check;
+pub mod preflight;
 pub mod relative_speed;
 pub mod scheduler;
 pub mod timing_result;
 
 use std::cmp;
+use std::process::ExitStatus;
+use std::time::Instant;
 
 use crate::benchmark::executor::BenchmarkIteration;
 use crate::command::Command;
+use crate::export::{Event, EventStream};
 use crate::options::{
-    CmdFailureAction, CommandOutputPolicy, ExecutorKind, Options, OutputStyleOption,
+    CmdFailureAction, ColdCache, CommandOutputPolicy, ExecutorKind, Options, OutlierPolicy,
+    OutputStyleOption,
+};
+use crate::outlier_detection::{
+    modified_zscores, outlier_indices, remove_outliers, winsorize, OUTLIER_THRESHOLD,
 };
-use crate::outlier_detection::{modified_zscores, OUTLIER_THRESHOLD};
 use crate::output::format::{format_duration, format_duration_unit};
+use crate::output::histogram::print_distribution;
 use crate::output::progress_bar::get_progress_bar;
 use crate::output::warnings::{OutlierWarningOptions, Warnings};
 use crate::parameter::ParameterNameAndValue;
 use crate::util::exit_code::extract_exit_code;
 use crate::util::min_max::{max, min};
+use crate::util::page_cache;
+use crate::util::percentile::percentile;
 use crate::util::units::Second;
-use benchmark_result::BenchmarkResult;
+use benchmark_result::{BenchmarkResult, Percentiles, PhaseTimes, Run, RunKind};
+use output_check::OutputDigest;
 use timing_result::TimingResult;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use colored::*;
 use statistical::{mean, median, standard_deviation};
 
@@ -36,6 +49,7 @@ pub struct Benchmark<'a> {
     command: &'a Command<'a>,
     options: &'a Options,
     executor: &'a dyn Executor,
+    events: &'a EventStream,
 }
 
 impl<'a> Benchmark<'a> {
@@ -44,12 +58,14 @@ impl<'a> Benchmark<'a> {
         command: &'a Command<'a>,
         options: &'a Options,
         executor: &'a dyn Executor,
+        events: &'a EventStream,
     ) -> Self {
         Benchmark {
             number,
             command,
             options,
             executor,
+            events,
         }
     }
 
@@ -77,11 +93,10 @@ impl<'a> Benchmark<'a> {
         parameters: impl IntoIterator<Item = ParameterNameAndValue<'a>>,
         output_policy: &CommandOutputPolicy,
     ) -> Result<TimingResult> {
-        let command = self
-            .options
-            .setup_command
-            .as_ref()
-            .map(|setup_command| Command::new_parametrized(None, setup_command, parameters));
+        let command = self.options.setup_command.as_ref().map(|setup_command| {
+            Command::new_parametrized(None, setup_command, parameters)
+                .with_environment_of(self.command)
+        });
 
         let error_output = "The setup command terminated with a non-zero exit code. \
                             Append ' || true' to the command if you are sure that this can be ignored.";
@@ -102,7 +117,10 @@ impl<'a> Benchmark<'a> {
             .options
             .cleanup_command
             .as_ref()
-            .map(|cleanup_command| Command::new_parametrized(None, cleanup_command, parameters));
+            .map(|cleanup_command| {
+                Command::new_parametrized(None, cleanup_command, parameters)
+                    .with_environment_of(self.command)
+            });
 
         let error_output = "The cleanup command terminated with a non-zero exit code. \
                             Append ' || true' to the command if you are sure that this can be ignored.";
@@ -125,6 +143,21 @@ impl<'a> Benchmark<'a> {
         self.run_intermediate_command(command, error_output, output_policy)
     }
 
+    /// Clear the page cache as requested by `--cold-cache` or `--cold-cache-file`.
+    fn clear_page_cache(&self) -> Result<()> {
+        if matches!(self.options.executor_kind, ExecutorKind::Mock(_)) {
+            return Ok(());
+        }
+
+        match &self.options.cold_cache {
+            None => Ok(()),
+            Some(ColdCache::DropAll) => page_cache::drop_page_cache(),
+            Some(ColdCache::EvictFiles(files)) => files
+                .iter()
+                .try_for_each(|file| page_cache::evict_file(file)),
+        }
+    }
+
     /// Run the command specified by `--conclude`.
     fn run_conclusion_command(
         &self,
@@ -137,6 +170,39 @@ impl<'a> Benchmark<'a> {
         self.run_intermediate_command(command, error_output, output_policy)
     }
 
+    /// Compare the output of a benchmark run with the expectation. Returns a description of
+    /// the mismatch if the outputs differ and '--output-mismatch=warn' is used.
+    fn check_output(
+        &self,
+        iteration: BenchmarkIteration,
+        actual: Option<OutputDigest>,
+        expected: Option<OutputDigest>,
+        expectation: &str,
+    ) -> Result<Option<String>> {
+        let (Some(actual), Some(expected)) = (actual, expected) else {
+            return Ok(None);
+        };
+        if actual == expected {
+            return Ok(None);
+        }
+
+        let run = match iteration {
+            BenchmarkIteration::Benchmark(0) => "the first benchmark run".to_string(),
+            BenchmarkIteration::Benchmark(i) => format!("benchmark iteration {i}"),
+            _ => "the output check run".to_string(),
+        };
+        let message = format!(
+            "The output of '{command}' in {run} (SHA-256: {actual}) differs from {expectation} \
+             (SHA-256: {expected}).",
+            command = self.command.get_name(),
+        );
+        if self.options.output_check.warn_only {
+            Ok(Some(message))
+        } else {
+            bail!("{message} Use '--output-mismatch=warn' to continue anyway.")
+        }
+    }
+
     /// Run the benchmark for a single command
     pub fn run(&self) -> Result<BenchmarkResult> {
         if self.options.output_style != OutputStyleOption::Disabled {
@@ -147,15 +213,60 @@ impl<'a> Benchmark<'a> {
                 self.command.get_name_with_unused_parameters(),
             );
         }
+        self.events.emit(Event::BenchmarkStart {
+            benchmark: self.number,
+            command: self.command.get_name(),
+        })?;
 
         let mut times_real: Vec<Second> = vec![];
         let mut times_user: Vec<Second> = vec![];
         let mut times_system: Vec<Second> = vec![];
         let mut memory_usage_byte: Vec<u64> = vec![];
         let mut exit_codes: Vec<Option<i32>> = vec![];
+        let mut runs: Vec<Run> = vec![];
         let mut all_succeeded = true;
 
-        let output_policy = &self.options.command_output_policies[self.number];
+        let record_run = |runs: &mut Vec<Run>,
+                          kind: RunKind,
+                          iteration: u64,
+                          start: Instant,
+                          res: &TimingResult,
+                          status: ExitStatus|
+         -> Result<()> {
+            let run = Run {
+                kind,
+                iteration,
+                start: start.duration_since(self.events.start()).as_secs_f64(),
+            };
+            self.events.emit(Event::Run {
+                benchmark: self.number,
+                run: &run,
+                time_real: res.time_real,
+                time_user: res.time_user,
+                time_system: res.time_system,
+                exit_code: extract_exit_code(status),
+            })?;
+            runs.push(run);
+            Ok(())
+        };
+
+        let output_policy = &self.options.command_output_policies[self.number]
+            .for_command(self.number, self.command);
+
+        // The output is only captured in the runs where it is checked. Unless every run is
+        // checked, this happens in an additional run that is not part of the measurements, as
+        // capturing and hashing the output affects the run time.
+        let output_check = &self.options.output_check;
+        let checked_output_policy = &if output_check.is_enabled() {
+            output_policy
+                .for_output_check()
+                .unwrap_or_else(|| output_policy.clone())
+        } else {
+            output_policy.clone()
+        };
+        let expected_output = output_check.expected_for(self.number);
+        let mut output_mismatch = None;
+        let mut first_output = None;
 
         let preparation_command = self.options.preparation_command.as_ref().map(|values| {
             let preparation_command = if values.len() == 1 {
@@ -168,13 +279,18 @@ impl<'a> Benchmark<'a> {
                 preparation_command,
                 self.command.get_parameters().iter().cloned(),
             )
+            .with_environment_of(self.command)
         });
 
-        let run_preparation_command = || {
-            preparation_command
+        let mut prepare_times: Vec<Second> = vec![];
+        let mut run_preparation_command = || {
+            self.clear_page_cache()?;
+            let result = preparation_command
                 .as_ref()
                 .map(|cmd| self.run_preparation_command(cmd, output_policy))
-                .transpose()
+                .transpose()?;
+            prepare_times.extend(result.map(|r| r.time_real));
+            Ok::<_, anyhow::Error>(result)
         };
 
         let conclusion_command = self.options.conclusion_command.as_ref().map(|values| {
@@ -188,17 +304,23 @@ impl<'a> Benchmark<'a> {
                 conclusion_command,
                 self.command.get_parameters().iter().cloned(),
             )
+            .with_environment_of(self.command)
         });
-        let run_conclusion_command = || {
-            conclusion_command
+        let mut conclude_times: Vec<Second> = vec![];
+        let mut run_conclusion_command = || {
+            let result = conclusion_command
                 .as_ref()
                 .map(|cmd| self.run_conclusion_command(cmd, output_policy))
-                .transpose()
+                .transpose()?;
+            conclude_times.extend(result.map(|r| r.time_real));
+            Ok::<_, anyhow::Error>(result)
         };
 
-        self.run_setup_command(self.command.get_parameters().iter().cloned(), output_policy)?;
+        let setup_result =
+            self.run_setup_command(self.command.get_parameters().iter().cloned(), output_policy)?;
 
         // Warmup phase
+        let mut warmup_times: Vec<Second> = vec![];
         if self.options.warmup_count > 0 {
             let progress_bar = if self.options.output_style != OutputStyleOption::Disabled {
                 Some(get_progress_bar(
@@ -212,12 +334,15 @@ impl<'a> Benchmark<'a> {
 
             for i in 0..self.options.warmup_count {
                 let _ = run_preparation_command()?;
-                let _ = self.executor.run_command_and_measure(
+                let start = Instant::now();
+                let (res, status) = self.executor.run_command_and_measure(
                     self.command,
                     BenchmarkIteration::Warmup(i),
                     None,
                     output_policy,
                 )?;
+                record_run(&mut runs, RunKind::Warmup, i, start, &res, status)?;
+                warmup_times.push(res.time_real);
                 let _ = run_conclusion_command()?;
                 if let Some(bar) = progress_bar.as_ref() {
                     bar.inc(1)
@@ -228,6 +353,30 @@ impl<'a> Benchmark<'a> {
             }
         }
 
+        if output_check.is_enabled() && !output_check.all_runs {
+            if let Some(cmd) = &preparation_command {
+                self.clear_page_cache()?;
+                self.run_preparation_command(cmd, output_policy)?;
+            }
+            let (res, _) = self.executor.run_command_and_measure(
+                self.command,
+                BenchmarkIteration::NonBenchmarkRun,
+                None,
+                checked_output_policy,
+            )?;
+            if let Some(cmd) = &conclusion_command {
+                self.run_conclusion_command(cmd, output_policy)?;
+            }
+
+            first_output = res.output_digest;
+            output_mismatch = self.check_output(
+                BenchmarkIteration::NonBenchmarkRun,
+                first_output,
+                expected_output,
+                "the expected output",
+            )?;
+        }
+
         // Set up progress bar (and spinner for initial measurement)
         let progress_bar = if self.options.output_style != OutputStyleOption::Disabled {
             Some(get_progress_bar(
@@ -244,13 +393,29 @@ impl<'a> Benchmark<'a> {
             preparation_result.map_or(0.0, |res| res.time_real + self.executor.time_overhead());
 
         // Initial timing run
+        let start = Instant::now();
         let (res, status) = self.executor.run_command_and_measure(
             self.command,
             BenchmarkIteration::Benchmark(0),
             None,
-            output_policy,
+            if output_check.all_runs {
+                checked_output_policy
+            } else {
+                output_policy
+            },
         )?;
         let success = status.success();
+        record_run(&mut runs, RunKind::Benchmark, 0, start, &res, status)?;
+
+        if output_check.all_runs {
+            first_output = res.output_digest;
+            output_mismatch = self.check_output(
+                BenchmarkIteration::Benchmark(0),
+                first_output,
+                expected_output,
+                "the expected output",
+            )?;
+        }
 
         let conclusion_result = run_conclusion_command()?;
         let conclusion_overhead =
@@ -306,13 +471,32 @@ impl<'a> Benchmark<'a> {
                 bar.set_message(msg.to_owned())
             }
 
+            let start = Instant::now();
             let (res, status) = self.executor.run_command_and_measure(
                 self.command,
                 BenchmarkIteration::Benchmark(i + 1),
                 None,
-                output_policy,
+                if output_check.all_runs {
+                    checked_output_policy
+                } else {
+                    output_policy
+                },
             )?;
             let success = status.success();
+            record_run(&mut runs, RunKind::Benchmark, i + 1, start, &res, status)?;
+
+            if output_check.all_runs {
+                let (expected, expectation) = match expected_output {
+                    Some(expected) => (Some(expected), "the expected output"),
+                    None => (first_output, "the output of the first run"),
+                };
+                output_mismatch = output_mismatch.or(self.check_output(
+                    BenchmarkIteration::Benchmark(i + 1),
+                    res.output_digest,
+                    expected,
+                    expectation,
+                )?);
+            }
 
             times_real.push(res.time_real);
             times_user.push(res.time_user);
@@ -333,26 +517,63 @@ impl<'a> Benchmark<'a> {
             bar.finish_and_clear()
         }
 
+        // Remove or clamp statistical outliers, if requested
+        let outliers = match self.options.outlier_policy {
+            OutlierPolicy::Keep => vec![],
+            OutlierPolicy::Remove | OutlierPolicy::Winsorize => outlier_indices(&times_real),
+        };
+        let (stats_real, stats_user, stats_system) = match self.options.outlier_policy {
+            OutlierPolicy::Keep => (times_real.clone(), times_user.clone(), times_system.clone()),
+            OutlierPolicy::Remove => (
+                remove_outliers(&times_real, &outliers),
+                remove_outliers(&times_user, &outliers),
+                remove_outliers(&times_system, &outliers),
+            ),
+            OutlierPolicy::Winsorize => (
+                winsorize(&times_real, &outliers),
+                winsorize(&times_user, &outliers),
+                winsorize(&times_system, &outliers),
+            ),
+        };
+
         // Compute statistical quantities
         let t_num = times_real.len();
-        let t_mean = mean(&times_real);
-        let t_stddev = if times_real.len() > 1 {
-            Some(standard_deviation(&times_real, Some(t_mean)))
+        let t_mean = mean(&stats_real);
+        let t_stddev = if stats_real.len() > 1 {
+            Some(standard_deviation(&stats_real, Some(t_mean)))
         } else {
             None
         };
-        let t_median = median(&times_real);
-        let t_min = min(&times_real);
-        let t_max = max(&times_real);
+        let t_median = median(&stats_real);
+        let t_min = min(&stats_real);
+        let t_max = max(&stats_real);
+        let t_percentiles = Percentiles(
+            self.options
+                .percentiles
+                .iter()
+                .map(|&p| (p, percentile(&stats_real, p)))
+                .collect(),
+        );
 
-        let user_mean = mean(&times_user);
-        let system_mean = mean(&times_system);
+        let user_mean = mean(&stats_user);
+        let system_mean = mean(&stats_system);
 
         // Formatting and console output
         let (mean_str, time_unit) = format_duration_unit(t_mean, self.options.time_unit);
         let min_str = format_duration(t_min, Some(time_unit));
         let max_str = format_duration(t_max, Some(time_unit));
-        let num_str = format!("{t_num} runs");
+        let num_str = {
+            let noun = if outliers.len() == 1 {
+                "outlier"
+            } else {
+                "outliers"
+            };
+            match (self.options.outlier_policy, outliers.len()) {
+                (OutlierPolicy::Keep, _) | (_, 0) => format!("{t_num} runs"),
+                (OutlierPolicy::Remove, n) => format!("{t_num} runs, {n} {noun} removed"),
+                (OutlierPolicy::Winsorize, n) => format!("{t_num} runs, {n} {noun} clamped"),
+            }
+        };
 
         let user_str = format_duration(user_mean, Some(time_unit));
         let system_str = format_duration(system_mean, Some(time_unit));
@@ -388,6 +609,10 @@ impl<'a> Benchmark<'a> {
                     max_str.purple(),
                     num_str.dimmed()
                 );
+
+                if self.options.show_histogram {
+                    print_distribution(&times_real, time_unit, self.options.output_style);
+                }
             }
         }
 
@@ -401,11 +626,23 @@ impl<'a> Benchmark<'a> {
             warnings.push(Warnings::FastExecutionTime);
         }
 
+        // Check for negative times after subtracting the shell spawning time
+        if let Some(calibration) = self.executor.calibration() {
+            if times_real.iter().any(|&t| t < 0.0) {
+                warnings.push(Warnings::NegativeRunTime(calibration));
+            }
+        }
+
         // Check program exit codes
         if !all_succeeded {
             warnings.push(Warnings::NonZeroExitCode);
         }
 
+        // Check program output
+        if let Some(message) = output_mismatch {
+            warnings.push(Warnings::OutputMismatch(message));
+        }
+
         // Run outlier detection
         let scores = modified_zscores(&times_real);
 
@@ -425,7 +662,9 @@ impl<'a> Benchmark<'a> {
                 times_real[0],
                 outlier_warning_options,
             ));
-        } else if scores.iter().any(|&s| s.abs() > OUTLIER_THRESHOLD) {
+        } else if self.options.outlier_policy == OutlierPolicy::Keep
+            && scores.iter().any(|&s| s.abs() > OUTLIER_THRESHOLD)
+        {
             warnings.push(Warnings::OutliersDetected(outlier_warning_options));
         }
 
@@ -441,9 +680,26 @@ impl<'a> Benchmark<'a> {
             println!(" ");
         }
 
-        self.run_cleanup_command(self.command.get_parameters().iter().cloned(), output_policy)?;
+        let cleanup_result =
+            self.run_cleanup_command(self.command.get_parameters().iter().cloned(), output_policy)?;
 
-        Ok(BenchmarkResult {
+        let phase_times = self.options.export_phase_times.then(|| PhaseTimes {
+            warmup: warmup_times,
+            prepare: prepare_times,
+            conclude: conclude_times,
+            setup: self
+                .options
+                .setup_command
+                .as_ref()
+                .map(|_| setup_result.time_real),
+            cleanup: self
+                .options
+                .cleanup_command
+                .as_ref()
+                .map(|_| cleanup_result.time_real),
+        });
+
+        let result = BenchmarkResult {
             command: self.command.get_name(),
             command_with_unused_parameters: self.command.get_name_with_unused_parameters(),
             mean: t_mean,
@@ -453,15 +709,31 @@ impl<'a> Benchmark<'a> {
             system: system_mean,
             min: t_min,
             max: t_max,
+            percentiles: t_percentiles,
             times: Some(times_real),
+            times_user: Some(times_user),
+            times_system: Some(times_system),
+            outliers,
+            outlier_policy: (self.options.outlier_policy != OutlierPolicy::Keep)
+                .then_some(self.options.outlier_policy),
             memory_usage_byte: Some(memory_usage_byte),
             exit_codes,
+            runs,
+            phase_times,
+            output_sha256: first_output.map(|digest| digest.to_string()),
+            calibration: self.executor.calibration(),
             parameters: self
                 .command
                 .get_parameters()
                 .iter()
                 .map(|(name, value)| (name.to_string(), value.to_string()))
                 .collect(),
-        })
+        };
+        self.events.emit(Event::BenchmarkEnd {
+            benchmark: self.number,
+            result: &result,
+        })?;
+
+        Ok(result)
     }
 }
@@ -0,0 +1,68 @@
+use std::fmt;
+use std::io::{self, Read};
+
+use sha2::{Digest, Sha256};
+
+/// SHA-256 digest of the output of a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputDigest([u8; 32]);
+
+impl OutputDigest {
+    /// Compute the digest of the given data
+    pub fn of(data: &[u8]) -> Self {
+        OutputDigest(Sha256::digest(data).into())
+    }
+
+    /// Compute the digest of everything that can be read from the given reader
+    pub fn of_reader<R: Read>(mut reader: R) -> io::Result<Self> {
+        const CHUNK_SIZE: usize = 64 << 10;
+
+        let mut hasher = Sha256::new();
+        let mut buf = vec![0; CHUNK_SIZE];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(bytes) => hasher.update(&buf[..bytes]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(OutputDigest(hasher.finalize().into()))
+    }
+
+    /// Parse a digest in hexadecimal notation (case-insensitive)
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        if hex.len() != 64 || !hex.is_ascii() {
+            return None;
+        }
+        let mut digest = [0u8; 32];
+        for (byte, chunk) in digest.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            let chunk = std::str::from_utf8(chunk).ok()?;
+            *byte = u8::from_str_radix(chunk, 16).ok()?;
+        }
+        Some(OutputDigest(digest))
+    }
+}
+
+impl fmt::Display for OutputDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_output_digest() {
+    const HELLO: &str = "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03";
+
+    let digest = OutputDigest::of(b"hello\n");
+    assert_eq!(digest.to_string(), HELLO);
+    assert_eq!(OutputDigest::of_reader(&b"hello\n"[..]).unwrap(), digest);
+
+    assert_eq!(OutputDigest::from_hex(HELLO), Some(digest));
+    assert_eq!(OutputDigest::from_hex(&HELLO.to_uppercase()), Some(digest));
+    assert_eq!(OutputDigest::from_hex(&HELLO[1..]), None);
+    assert_eq!(OutputDigest::from_hex(&HELLO.replace('5', "g")), None);
+}
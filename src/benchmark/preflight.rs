@@ -0,0 +1,161 @@
+use std::thread;
+use std::time::Duration;
+
+use crate::output::warnings::Warnings;
+use crate::util::system_info;
+
+/// Time span during which the swap activity is monitored
+const SWAP_SAMPLE_DURATION: Duration = Duration::from_millis(100);
+
+/// Load average (per CPU core) above which the system is considered to be busy
+const MAX_LOAD_PER_CORE: f64 = 0.5;
+
+/// Frequency scaling drivers which implement 'powersave' as a dynamic governor (the default
+/// on most systems), as opposed to keeping the CPUs at their lowest frequency
+const DYNAMIC_POWERSAVE_DRIVERS: &[&str] = &["intel_pstate", "amd-pstate-epp"];
+
+/// The state of the system with respect to common sources of measurement noise
+#[derive(Debug, Default)]
+pub struct SystemState {
+    /// Frequency scaling governors of all CPUs
+    pub cpu_governors: Vec<String>,
+
+    /// Frequency scaling driver
+    pub cpu_frequency_driver: Option<String>,
+
+    /// Control file and value to disable turbo boost, if it is enabled
+    pub turbo_boost: Option<(&'static str, &'static str)>,
+
+    /// Address space layout randomization setting
+    pub aslr: Option<u8>,
+
+    /// Load average over the last minute
+    pub load_average: Option<f64>,
+
+    /// Number of CPU cores
+    pub cpu_cores: usize,
+
+    /// Number of pages that have been swapped in or out during `SWAP_SAMPLE_DURATION` (only
+    /// sampled in strict mode)
+    pub swapped_pages: Option<u64>,
+}
+
+impl SystemState {
+    /// Query the current state of the system. This only collects information on Linux. The
+    /// swap activity is only sampled in strict mode, as this takes some time.
+    pub fn probe(strict: bool) -> Self {
+        let swapped_before = system_info::swapped_pages().filter(|_| strict);
+        if swapped_before.is_some() {
+            thread::sleep(SWAP_SAMPLE_DURATION);
+        }
+        let swapped_pages = swapped_before
+            .zip(system_info::swapped_pages())
+            .map(|(before, after)| after.saturating_sub(before));
+
+        SystemState {
+            cpu_governors: system_info::cpu_governors(),
+            cpu_frequency_driver: system_info::cpu_frequency_driver(),
+            turbo_boost: system_info::turbo_boost(),
+            aslr: system_info::aslr_setting(),
+            load_average: system_info::load_average().map(|l| l[0]),
+            cpu_cores: system_info::cpu_cores().unwrap_or(1),
+            swapped_pages,
+        }
+    }
+
+    /// Check the system state for conditions that are likely to disturb the measurements.
+    /// Enabled ASLR is the default on Linux, so it is only reported in strict mode.
+    pub fn check(&self, strict: bool) -> Vec<Warnings> {
+        let mut warnings = vec![];
+
+        let dynamic_powersave = self
+            .cpu_frequency_driver
+            .as_deref()
+            .is_some_and(|driver| DYNAMIC_POWERSAVE_DRIVERS.contains(&driver));
+        let mut slow_governors: Vec<&str> = self
+            .cpu_governors
+            .iter()
+            .map(String::as_str)
+            .filter(|&g| g == "powersave" && !dynamic_powersave)
+            .collect();
+        slow_governors.sort_unstable();
+        slow_governors.dedup();
+        if !slow_governors.is_empty() {
+            warnings.push(Warnings::CpuGovernor(slow_governors.join("', '")));
+        }
+
+        if let Some((path, value)) = self.turbo_boost {
+            warnings.push(Warnings::TurboBoostEnabled(path, value));
+        }
+
+        if strict && self.aslr.is_some_and(|aslr| aslr > 0) {
+            warnings.push(Warnings::AslrEnabled);
+        }
+
+        if let Some(load) = self.load_average {
+            if load > (MAX_LOAD_PER_CORE * self.cpu_cores as f64).max(1.0) {
+                warnings.push(Warnings::HighSystemLoad(load));
+            }
+        }
+
+        if let Some(pages) = self.swapped_pages.filter(|&p| p > 0) {
+            warnings.push(Warnings::SwapActivity(
+                pages,
+                SWAP_SAMPLE_DURATION.as_secs_f64(),
+            ));
+        }
+
+        warnings
+    }
+}
+
+#[test]
+fn test_quiet_system() {
+    let state = SystemState {
+        cpu_governors: vec!["performance".into(), "schedutil".into()],
+        cpu_frequency_driver: Some("acpi-cpufreq".into()),
+        turbo_boost: None,
+        aslr: Some(0),
+        load_average: Some(0.9),
+        cpu_cores: 2,
+        swapped_pages: Some(0),
+    };
+    assert!(state.check(true).is_empty());
+
+    // Nothing is reported if the information is not available
+    assert!(SystemState::default().check(true).is_empty());
+}
+
+#[test]
+fn test_noisy_system() {
+    let state = SystemState {
+        cpu_governors: vec!["powersave".into(), "powersave".into(), "performance".into()],
+        cpu_frequency_driver: Some("acpi-cpufreq".into()),
+        turbo_boost: Some(("/sys/devices/system/cpu/intel_pstate/no_turbo", "1")),
+        aslr: Some(2),
+        load_average: Some(3.5),
+        cpu_cores: 4,
+        swapped_pages: Some(42),
+    };
+
+    let warnings = state.check(true);
+    assert_eq!(warnings.len(), 5);
+    assert!(matches!(&warnings[0], Warnings::CpuGovernor(g) if g == "powersave"));
+    assert!(matches!(warnings[1], Warnings::TurboBoostEnabled(_, "1")));
+    assert!(matches!(warnings[2], Warnings::AslrEnabled));
+    assert!(matches!(warnings[3], Warnings::HighSystemLoad(l) if l == 3.5));
+    assert!(matches!(warnings[4], Warnings::SwapActivity(42, _)));
+
+    // ASLR is only reported in strict mode
+    assert_eq!(state.check(false).len(), 4);
+}
+
+#[test]
+fn test_dynamic_powersave_governor() {
+    let state = SystemState {
+        cpu_governors: vec!["powersave".into(), "powersave".into()],
+        cpu_frequency_driver: Some("intel_pstate".into()),
+        ..Default::default()
+    };
+    assert!(state.check(true).is_empty());
+}
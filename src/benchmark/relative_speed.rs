@@ -1,7 +1,8 @@
 use std::cmp::Ordering;
 
 use super::benchmark_result::BenchmarkResult;
-use crate::{options::SortOrder, util::units::Scalar};
+use crate::options::{SortOrder, SummaryStatistic};
+use crate::util::units::Scalar;
 
 #[derive(Debug)]
 pub struct BenchmarkResultWithRelativeSpeed<'a> {
@@ -13,14 +14,20 @@ pub struct BenchmarkResultWithRelativeSpeed<'a> {
     pub relative_ordering: Ordering,
 }
 
-pub fn compare_mean_time(l: &BenchmarkResult, r: &BenchmarkResult) -> Ordering {
-    l.mean.partial_cmp(&r.mean).unwrap_or(Ordering::Equal)
+pub fn compare_statistic(
+    l: &BenchmarkResult,
+    r: &BenchmarkResult,
+    statistic: SummaryStatistic,
+) -> Ordering {
+    l.statistic(statistic)
+        .partial_cmp(&r.statistic(statistic))
+        .unwrap_or(Ordering::Equal)
 }
 
-pub fn fastest_of(results: &[BenchmarkResult]) -> &BenchmarkResult {
+pub fn fastest_of(results: &[BenchmarkResult], statistic: SummaryStatistic) -> &BenchmarkResult {
     results
         .iter()
-        .min_by(|&l, &r| compare_mean_time(l, r))
+        .min_by(|&l, &r| compare_statistic(l, r, statistic))
         .expect("at least one benchmark result")
 }
 
@@ -28,17 +35,27 @@ fn compute_relative_speeds<'a>(
     results: &'a [BenchmarkResult],
     reference: &'a BenchmarkResult,
     sort_order: SortOrder,
+    statistic: SummaryStatistic,
 ) -> Vec<BenchmarkResultWithRelativeSpeed<'a>> {
+    // Negative times (after subtracting the shell spawning time) are treated as zero, such
+    // that the relative speeds are never negative
+    let reference_value = reference.statistic(statistic).max(0.0);
+
     let mut results: Vec<_> = results
         .iter()
         .map(|result| {
             let is_reference = result == reference;
-            let relative_ordering = compare_mean_time(result, reference);
+            let relative_ordering = compare_statistic(result, reference, statistic);
+            let value = result.statistic(statistic).max(0.0);
 
-            if result.mean == 0.0 {
+            if value == 0.0 || reference_value == 0.0 {
                 return BenchmarkResultWithRelativeSpeed {
                     result,
-                    relative_speed: if is_reference { 1.0 } else { f64::INFINITY },
+                    relative_speed: if is_reference || value == reference_value {
+                        1.0
+                    } else {
+                        f64::INFINITY
+                    },
                     relative_speed_stddev: None,
                     is_reference,
                     relative_ordering,
@@ -46,20 +63,25 @@ fn compute_relative_speeds<'a>(
             }
 
             let ratio = match relative_ordering {
-                Ordering::Less => reference.mean / result.mean,
+                Ordering::Less => reference_value / value,
                 Ordering::Equal => 1.0,
-                Ordering::Greater => result.mean / reference.mean,
+                Ordering::Greater => value / reference_value,
             };
 
             // https://en.wikipedia.org/wiki/Propagation_of_uncertainty#Example_formulas
-            // Covariance asssumed to be 0, i.e. variables are assumed to be independent
+            // Covariance asssumed to be 0, i.e. variables are assumed to be independent. The
+            // standard deviation is only an uncertainty of the mean, not of other statistics.
             let ratio_stddev = match (result.stddev, reference.stddev) {
-                (Some(result_stddev), Some(fastest_stddev)) => Some(
-                    ratio
-                        * ((result_stddev / result.mean).powi(2)
-                            + (fastest_stddev / reference.mean).powi(2))
-                        .sqrt(),
-                ),
+                (Some(result_stddev), Some(fastest_stddev))
+                    if statistic == SummaryStatistic::Mean =>
+                {
+                    Some(
+                        ratio
+                            * ((result_stddev / value).powi(2)
+                                + (fastest_stddev / reference_value).powi(2))
+                            .sqrt(),
+                    )
+                }
                 _ => None,
             };
 
@@ -76,7 +98,7 @@ fn compute_relative_speeds<'a>(
     match sort_order {
         SortOrder::Command => {}
         SortOrder::MeanTime => {
-            results.sort_unstable_by(|r1, r2| compare_mean_time(r1.result, r2.result));
+            results.sort_unstable_by(|r1, r2| compare_statistic(r1.result, r2.result, statistic));
         }
     }
 
@@ -87,41 +109,48 @@ pub fn compute_with_check_from_reference<'a>(
     results: &'a [BenchmarkResult],
     reference: &'a BenchmarkResult,
     sort_order: SortOrder,
+    statistic: SummaryStatistic,
 ) -> Option<Vec<BenchmarkResultWithRelativeSpeed<'a>>> {
-    if fastest_of(results).mean == 0.0 || reference.mean == 0.0 {
+    if fastest_of(results, statistic).statistic(statistic) <= 0.0
+        || reference.statistic(statistic) <= 0.0
+    {
         return None;
     }
 
-    Some(compute_relative_speeds(results, reference, sort_order))
+    Some(compute_relative_speeds(
+        results, reference, sort_order, statistic,
+    ))
 }
 
 pub fn compute_with_check(
     results: &[BenchmarkResult],
     sort_order: SortOrder,
+    statistic: SummaryStatistic,
 ) -> Option<Vec<BenchmarkResultWithRelativeSpeed<'_>>> {
-    let fastest = fastest_of(results);
+    let fastest = fastest_of(results, statistic);
 
-    if fastest.mean == 0.0 {
+    if fastest.statistic(statistic) <= 0.0 {
         return None;
     }
 
-    Some(compute_relative_speeds(results, fastest, sort_order))
+    Some(compute_relative_speeds(
+        results, fastest, sort_order, statistic,
+    ))
 }
 
 /// Same as compute_with_check, potentially resulting in relative speeds of infinity
 pub fn compute(
     results: &[BenchmarkResult],
     sort_order: SortOrder,
+    statistic: SummaryStatistic,
 ) -> Vec<BenchmarkResultWithRelativeSpeed<'_>> {
-    let fastest = fastest_of(results);
+    let fastest = fastest_of(results, statistic);
 
-    compute_relative_speeds(results, fastest, sort_order)
+    compute_relative_speeds(results, fastest, sort_order, statistic)
 }
 
 #[cfg(test)]
 fn create_result(name: &str, mean: Scalar) -> BenchmarkResult {
-    use std::collections::BTreeMap;
-
     BenchmarkResult {
         command: name.into(),
         command_with_unused_parameters: name.into(),
@@ -132,10 +161,7 @@ fn create_result(name: &str, mean: Scalar) -> BenchmarkResult {
         system: 0.0,
         min: mean,
         max: mean,
-        times: None,
-        memory_usage_byte: None,
-        exit_codes: Vec::new(),
-        parameters: BTreeMap::new(),
+        ..Default::default()
     }
 }
 
@@ -149,7 +175,8 @@ fn test_compute_relative_speed() {
         create_result("cmd3", 5.0),
     ];
 
-    let annotated_results = compute_with_check(&results, SortOrder::Command).unwrap();
+    let annotated_results =
+        compute_with_check(&results, SortOrder::Command, SummaryStatistic::Mean).unwrap();
 
     assert_relative_eq!(1.5, annotated_results[0].relative_speed);
     assert_relative_eq!(1.0, annotated_results[1].relative_speed);
@@ -163,8 +190,13 @@ fn test_compute_relative_speed_with_reference() {
     let results = vec![create_result("cmd2", 2.0), create_result("cmd3", 5.0)];
     let reference = create_result("cmd2", 4.0);
 
-    let annotated_results =
-        compute_with_check_from_reference(&results, &reference, SortOrder::Command).unwrap();
+    let annotated_results = compute_with_check_from_reference(
+        &results,
+        &reference,
+        SortOrder::Command,
+        SummaryStatistic::Mean,
+    )
+    .unwrap();
 
     assert_relative_eq!(2.0, annotated_results[0].relative_speed);
     assert_relative_eq!(1.25, annotated_results[1].relative_speed);
@@ -174,7 +206,50 @@ fn test_compute_relative_speed_with_reference() {
 fn test_compute_relative_speed_for_zero_times() {
     let results = vec![create_result("cmd1", 1.0), create_result("cmd2", 0.0)];
 
-    let annotated_results = compute_with_check(&results, SortOrder::Command);
+    let annotated_results =
+        compute_with_check(&results, SortOrder::Command, SummaryStatistic::Mean);
 
     assert!(annotated_results.is_none());
 }
+
+#[test]
+fn test_compute_relative_speed_for_negative_times() {
+    let results = vec![
+        create_result("cmd1", 1.0),
+        create_result("cmd2", -0.5),
+        create_result("cmd3", -0.1),
+    ];
+
+    let annotated_results = compute(&results, SortOrder::Command, SummaryStatistic::Mean);
+
+    assert_eq!(annotated_results[0].relative_speed, f64::INFINITY);
+    assert_eq!(annotated_results[1].relative_speed, 1.0);
+    assert!(annotated_results[1].is_reference);
+    assert_eq!(annotated_results[2].relative_speed, 1.0);
+    assert!(annotated_results
+        .iter()
+        .all(|r| r.relative_speed_stddev.is_none()));
+}
+
+#[test]
+fn test_compute_relative_speed_by_statistic() {
+    use approx::assert_relative_eq;
+
+    let mut cmd1 = create_result("cmd1", 3.0);
+    cmd1.min = 1.0;
+    let mut cmd2 = create_result("cmd2", 2.0);
+    cmd2.min = 2.0;
+    let results = vec![cmd1, cmd2];
+
+    let by_mean =
+        compute_with_check(&results, SortOrder::MeanTime, SummaryStatistic::Mean).unwrap();
+    assert_eq!(by_mean[0].result.command, "cmd2");
+    assert_relative_eq!(1.5, by_mean[1].relative_speed);
+
+    let by_min = compute_with_check(&results, SortOrder::MeanTime, SummaryStatistic::Min).unwrap();
+    assert_eq!(by_min[0].result.command, "cmd1");
+    assert!(by_min[0].is_reference);
+    assert_relative_eq!(2.0, by_min[1].relative_speed);
+    assert!(by_mean[1].relative_speed_stddev.is_some());
+    assert!(by_min[1].relative_speed_stddev.is_none());
+}
@@ -1,19 +1,23 @@
 use super::benchmark_result::BenchmarkResult;
 use super::executor::{Executor, MockExecutor, RawExecutor, ShellExecutor};
+use super::preflight::SystemState;
 use super::{relative_speed, Benchmark};
 use colored::*;
 use std::cmp::Ordering;
 
 use crate::command::{Command, Commands};
-use crate::export::ExportManager;
-use crate::options::{ExecutorKind, Options, OutputStyleOption, SortOrder};
+use crate::export::{Event, EventStream, ExportManager};
+use crate::options::{ExecutorKind, Options, OutputStyleOption, SortOrder, SummaryStatistic};
+use crate::output::format::{format_duration, format_duration_unit};
+use crate::output::warnings::Warnings;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 
 pub struct Scheduler<'a> {
     commands: &'a Commands<'a>,
     options: &'a Options,
     export_manager: &'a ExportManager,
+    events: &'a EventStream,
     results: Vec<BenchmarkResult>,
 }
 
@@ -22,16 +26,22 @@ impl<'a> Scheduler<'a> {
         commands: &'a Commands,
         options: &'a Options,
         export_manager: &'a ExportManager,
+        events: &'a EventStream,
     ) -> Self {
         Self {
             commands,
             options,
             export_manager,
+            events,
             results: vec![],
         }
     }
 
     pub fn run_benchmarks(&mut self) -> Result<()> {
+        if !matches!(self.options.executor_kind, ExecutorKind::Mock(_)) {
+            self.check_environment()?;
+        }
+
         let mut executor: Box<dyn Executor> = match self.options.executor_kind {
             ExecutorKind::Raw => Box::new(RawExecutor::new(self.options)),
             ExecutorKind::Mock(ref shell) => Box::new(MockExecutor::new(shell.clone())),
@@ -44,11 +54,52 @@ impl<'a> Scheduler<'a> {
             .as_ref()
             .map(|cmd| Command::new(self.options.reference_name.as_deref(), cmd));
 
+        self.events.emit(Event::SessionStart {
+            metadata: self.export_manager.metadata(),
+            commands: reference
+                .iter()
+                .chain(self.commands.iter())
+                .map(|cmd| cmd.get_name())
+                .collect(),
+        })?;
+
+        // The end of the session is also reported if one of the benchmarks fails
+        let result = self.run_each_benchmark(&mut *executor, reference.as_ref());
+        self.events.emit(Event::SessionEnd {
+            end_time: self.export_manager.metadata().finished_now().end_time,
+            error: result.as_ref().err().map(|e| format!("{e:#}")),
+        })?;
+
+        result
+    }
+
+    fn run_each_benchmark(
+        &mut self,
+        executor: &mut dyn Executor,
+        reference: Option<&Command>,
+    ) -> Result<()> {
         executor.calibrate()?;
+        self.report_calibration(executor)?;
+
+        for (number, cmd) in reference
+            .into_iter()
+            .chain(self.commands.iter())
+            .enumerate()
+        {
+            if number > 0 && self.options.recalibrate {
+                executor.calibrate()?;
+                self.report_calibration(executor)?;
+            }
 
-        for (number, cmd) in reference.iter().chain(self.commands.iter()).enumerate() {
+            let cmd = cmd.clone().with_environment(
+                &self.options.environment,
+                self.options.working_directory_for(number),
+            );
             self.results
-                .push(Benchmark::new(number, cmd, self.options, &*executor).run()?);
+                .push(Benchmark::new(number, &cmd, self.options, executor, self.events).run()?);
+            if self.options.output_check.identical {
+                self.check_identical_output()?;
+            }
 
             // We export results after each individual benchmark, because
             // we would risk losing them if a later benchmark fails.
@@ -58,6 +109,110 @@ impl<'a> Scheduler<'a> {
         Ok(())
     }
 
+    /// Use previously exported results instead of running the benchmarks ('--from-json')
+    pub fn import_results(&mut self, results: Vec<BenchmarkResult>) -> Result<()> {
+        self.results = results;
+        self.export_manager.write_results(&self.results, true)
+    }
+
+    /// Add the result of the shell spawning time measurement to the event stream and show it
+    /// (only with '--show-calibration')
+    fn report_calibration(&self, executor: &dyn Executor) -> Result<()> {
+        let Some(calibration) = executor.calibration() else {
+            return Ok(());
+        };
+        self.events.emit(Event::Calibration(&calibration))?;
+        if !self.options.show_calibration
+            || self.options.output_style == OutputStyleOption::Disabled
+        {
+            return Ok(());
+        }
+
+        let (median_str, unit) =
+            format_duration_unit(calibration.time_real, self.options.time_unit);
+        println!(
+            "{}",
+            format!(
+                "Shell spawning time (median of {} runs): {} ± {}  [Mean: {}, σ: {}]",
+                calibration.count,
+                median_str,
+                format_duration(calibration.uncertainty, Some(unit)),
+                format_duration(calibration.mean, Some(unit)),
+                format_duration(calibration.stddev, Some(unit)),
+            )
+            .dimmed()
+        );
+        println!(" ");
+
+        Ok(())
+    }
+
+    /// Make sure that the latest benchmarked command produced the same output as the first one
+    fn check_identical_output(&self) -> Result<()> {
+        let (first, last) = (&self.results[0], &self.results[self.results.len() - 1]);
+        let (Some(first_output), Some(last_output)) = (&first.output_sha256, &last.output_sha256)
+        else {
+            return Ok(());
+        };
+        if first_output == last_output {
+            return Ok(());
+        }
+
+        let message = format!(
+            "The output of '{}' (SHA-256: {last_output}) differs from the output of '{}' \
+             (SHA-256: {first_output}).",
+            last.command, first.command
+        );
+        if !self.options.output_check.warn_only {
+            bail!("{message} Use '--output-mismatch=warn' to continue anyway.");
+        }
+        eprintln!("{}: {}", "Warning".yellow(), message);
+        eprintln!(" ");
+
+        Ok(())
+    }
+
+    /// Report system settings that are likely to disturb the measurements. With
+    /// '--strict-environment', refuse to run the benchmarks in this case.
+    fn check_environment(&self) -> Result<()> {
+        let strict = self.options.strict_environment;
+        let warnings = SystemState::probe(strict).check(strict);
+        if warnings.is_empty() {
+            return Ok(());
+        }
+
+        for warning in &warnings {
+            eprintln!("{}: {}", "Warning".yellow(), warning);
+        }
+
+        // ASLR is enabled by default on most systems, so it is not considered fatal
+        if strict
+            && warnings
+                .iter()
+                .any(|warning| !matches!(warning, Warnings::AslrEnabled))
+        {
+            bail!(
+                "The system is not in a suitable state for benchmarking (see warnings above). \
+                 Remove the '--strict-environment' option to run the benchmarks anyway."
+            );
+        }
+        eprintln!(" ");
+
+        Ok(())
+    }
+
+    /// Print a table with the columns that have been selected via '--export-columns'
+    pub fn print_summary_table(&self) {
+        if self.options.output_style == OutputStyleOption::Disabled {
+            return;
+        }
+
+        if let Some(table) = self.export_manager.summary_table(&self.results) {
+            print!("{table}");
+            println!(" ");
+        }
+    }
+
     pub fn print_relative_speed_comparison(&self) {
         if self.options.output_style == OutputStyleOption::Disabled {
             return;
@@ -72,16 +227,21 @@ impl<'a> Scheduler<'a> {
             .reference_command
             .as_ref()
             .map(|_| &self.results[0])
-            .unwrap_or_else(|| relative_speed::fastest_of(&self.results));
+            .unwrap_or_else(|| relative_speed::fastest_of(&self.results, self.options.statistic));
 
         if let Some(annotated_results) = relative_speed::compute_with_check_from_reference(
             &self.results,
             reference,
             self.options.sort_order_speed_comparison,
+            self.options.statistic,
         ) {
             match self.options.sort_order_speed_comparison {
                 SortOrder::MeanTime => {
-                    println!("{}", "Summary".bold());
+                    if self.options.statistic == SummaryStatistic::Mean {
+                        println!("{}", "Summary".bold());
+                    } else {
+                        println!("{} (by {})", "Summary".bold(), self.options.statistic);
+                    }
 
                     let reference = annotated_results.iter().find(|r| r.is_reference).unwrap();
                     let others = annotated_results.iter().filter(|r| !r.is_reference);
@@ -142,7 +302,7 @@ impl<'a> Scheduler<'a> {
             }
         } else {
             eprintln!(
-                "{}: The benchmark comparison could not be computed as some benchmark times are zero. \
+                "{}: The benchmark comparison could not be computed as some benchmark times are zero or negative. \
                  This could be caused by background interference during the initial calibration phase \
                  of hyperfine, in combination with very fast commands (faster than a few milliseconds). \
                  Try to re-run the benchmark on a quiet system. If you did not do so already, try the \
@@ -161,6 +321,7 @@ impl<'a> Scheduler<'a> {
 #[cfg(test)]
 fn generate_results(args: &[&'static str]) -> Result<Vec<BenchmarkResult>> {
     use crate::cli::get_cli_arguments;
+    use crate::export::Metadata;
 
     let args = ["hyperfine", "--debug-mode", "--style=none"]
         .iter()
@@ -175,14 +336,23 @@ fn generate_results(args: &[&'static str]) -> Result<Vec<BenchmarkResult>> {
         &cli_arguments,
         options.time_unit,
         options.sort_order_exports,
+        options.statistic,
+        Metadata::collect(&options.executor_kind),
     )?;
 
     options.validate_against_command_list(&commands)?;
 
-    let mut scheduler = Scheduler::new(&commands, &options, &export_manager);
+    let events = EventStream::default();
+    let mut scheduler = Scheduler::new(&commands, &options, &export_manager, &events);
 
     scheduler.run_benchmarks()?;
-    Ok(scheduler.results)
+
+    // The start times of the runs are not deterministic
+    let mut results = scheduler.results;
+    for run in results.iter_mut().flat_map(|result| result.runs.iter_mut()) {
+        run.start = 0.0;
+    }
+    Ok(results)
 }
 
 #[test]
@@ -196,15 +366,33 @@ fn scheduler_basic() -> Result<()> {
       system: 0
       min: 0.123
       max: 0.123
+      percentiles:
+        p50: 0.123
+        p90: 0.123
+        p95: 0.123
+        p99: 0.123
       times:
         - 0.123
         - 0.123
+      times_user:
+        - 0
+        - 0
+      times_system:
+        - 0
+        - 0
       memory_usage_byte:
         - 0
         - 0
       exit_codes:
         - 0
         - 0
+      runs:
+        - kind: benchmark
+          iteration: 0
+          start: 0
+        - kind: benchmark
+          iteration: 1
+          start: 0
     - command: sleep 0.456
       mean: 0.456
       stddev: 0
@@ -213,15 +401,33 @@ fn scheduler_basic() -> Result<()> {
       system: 0
       min: 0.456
       max: 0.456
+      percentiles:
+        p50: 0.456
+        p90: 0.456
+        p95: 0.456
+        p99: 0.456
       times:
         - 0.456
         - 0.456
+      times_user:
+        - 0
+        - 0
+      times_system:
+        - 0
+        - 0
       memory_usage_byte:
         - 0
         - 0
       exit_codes:
         - 0
         - 0
+      runs:
+        - kind: benchmark
+          iteration: 0
+          start: 0
+        - kind: benchmark
+          iteration: 1
+          start: 0
     "#);
 
     Ok(())
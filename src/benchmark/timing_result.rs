@@ -1,5 +1,7 @@
 use crate::util::units::Second;
 
+use super::output_check::OutputDigest;
+
 /// Results from timing a single command
 #[derive(Debug, Default, Copy, Clone)]
 pub struct TimingResult {
@@ -14,4 +16,7 @@ pub struct TimingResult {
 
     /// Maximum amount of memory used, in bytes
     pub memory_usage_byte: u64,
+
+    /// Digest of the standard output (only computed if the output is checked)
+    pub output_digest: Option<OutputDigest>,
 }
@@ -30,7 +30,7 @@ fn build_command() -> Command {
                        The latter is only available if the shell is not explicitly disabled via \
                        '--shell=none'. If multiple commands are given, hyperfine will show a \
                        comparison of the respective runtimes.")
-                .required(true)
+                .required_unless_present("from-json")
                 .action(ArgAction::Append)
                 .value_hint(ValueHint::CommandString)
                 .value_parser(NonEmptyStringValueParser::new()),
@@ -200,6 +200,20 @@ fn build_command() -> Command {
                      possible parameter combinations.\n"
                 ),
         )
+        .arg(
+            Arg::new("parameter-env")
+                .long("parameter-env")
+                .action(ArgAction::Append)
+                .value_name("VAR")
+                .help(
+                    "Pass the parameter VAR (defined via --parameter-scan or --parameter-list) to \
+                     the benchmarked commands as an environment variable of the same name. This \
+                     can be used instead of (or in addition to) the '{VAR}' substitution. The \
+                     option can be specified multiple times.\n\n  \
+                     Example:  hyperfine -P RAYON_NUM_THREADS 1 16 --parameter-env RAYON_NUM_THREADS './app'\n\n\
+                     This performs benchmarks for './app' with RAYON_NUM_THREADS=1, …, RAYON_NUM_THREADS=16.",
+                ),
+        )
         .arg(
             Arg::new("shell")
                 .long("shell")
@@ -223,6 +237,25 @@ fn build_command() -> Command {
                 .conflicts_with_all(["shell", "debug-mode"])
                 .help("An alias for '--shell=none'.")
         )
+        .arg(
+            Arg::new("recalibrate")
+                .long("recalibrate")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Measure the shell spawning time again before each benchmarked command, instead \
+                     of only once at the start. This avoids a bias due to drifts of the spawning time \
+                     in long benchmark sessions.",
+                ),
+        )
+        .arg(
+            Arg::new("show-calibration")
+                .long("show-calibration")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Print the measured shell spawning time (median, uncertainty, mean and standard \
+                     deviation of the calibration runs) before the benchmarks.",
+                ),
+        )
         .arg(
             Arg::new("ignore-failure")
                 .long("ignore-failure")
@@ -251,6 +284,17 @@ fn build_command() -> Command {
                      the output of the tool.",
                 ),
         )
+        .arg(
+            Arg::new("histogram")
+                .long("histogram")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Show a histogram of the individual run times and a sparkline of the run \
+                     times in the order they were measured below the summary of each command. \
+                     This can help to spot bimodal distributions, long tails or drift over time. \
+                     With '--style=basic' or '--style=nocolor', only ASCII characters are used.",
+                ),
+        )
         .arg(
             Arg::new("sort")
             .long("sort")
@@ -261,13 +305,147 @@ fn build_command() -> Command {
             .hide_default_value(true)
             .help(
                 "Specify the sort order of the speed comparison summary and the exported tables for \
-                 markup formats (Markdown, AsciiDoc, org-mode):\n  \
+                 markup formats (Markdown, AsciiDoc, org-mode, LaTeX, reStructuredText):\n  \
                    * 'auto' (default): the speed comparison will be ordered by time and\n    \
                      the markup tables will be ordered by command (input order).\n  \
                    * 'command': order benchmarks in the way they were specified\n  \
-                   * 'mean-time': order benchmarks by mean runtime\n"
+                   * 'mean-time': order benchmarks by mean runtime (or by the statistic\n    \
+                     selected with --statistic)\n"
             ),
         )
+        .arg(
+            Arg::new("statistic")
+                .long("statistic")
+                .action(ArgAction::Set)
+                .value_name("STAT")
+                .help("Select the statistic of the run times that is used for the relative speed \
+                       comparison, for sorting by time (see --sort) and for the 'Relative' column \
+                       of the markup exports. Possible values: mean (default), median, min, or a \
+                       percentile like p95 (which is added to --percentiles if necessary). The \
+                       median or the minimum can be more robust for noisy benchmarks. The \
+                       uncertainty of the relative speed is only shown for the mean."),
+        )
+        .arg(
+            Arg::new("env")
+                .long("env")
+                .action(ArgAction::Append)
+                .value_name("KEY=VALUE")
+                .help(
+                    "Set additional environment variables for all benchmarked commands, e.g. \
+                     --env RUST_LOG=debug. The option can be specified multiple times, and multiple \
+                     variables can be set with a single option by separating them with whitespace: \
+                     --env 'RUST_LOG=debug MALLOC_ARENA_MAX=2'. The values can contain parameters \
+                     like {threads}. The variables are also set for the --setup, --prepare, \
+                     --conclude and --cleanup commands. In contrast to an 'env' wrapper, this does \
+                     not spawn an additional process.",
+                ),
+        )
+        .arg(
+            Arg::new("cwd")
+                .long("cwd")
+                .action(ArgAction::Append)
+                .value_name("DIR")
+                .value_hint(ValueHint::DirPath)
+                .help(
+                    "Run the benchmarked commands in the given working directory. The path can \
+                     contain parameters like {version}. The option can be specified once for all \
+                     commands or N times, where N is the number of benchmark commands (including \
+                     a potential reference). The --setup, --prepare, --conclude and --cleanup \
+                     commands run in the same directory.",
+                ),
+        )
+        .arg(
+            Arg::new("cold-cache")
+                .long("cold-cache")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("cold-cache-file")
+                .help(
+                    "Write dirty pages to disk and drop the page cache (as well as dentries and \
+                     inodes) before each run, to benchmark with a cold file system cache. This is \
+                     a native replacement for \
+                     --prepare 'sync; echo 3 | sudo tee /proc/sys/vm/drop_caches' and requires \
+                     root privileges. Only supported on Linux.",
+                ),
+        )
+        .arg(
+            Arg::new("cold-cache-file")
+                .long("cold-cache-file")
+                .action(ArgAction::Append)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help(
+                    "Evict the given FILE from the page cache before each run. This option can be \
+                     specified multiple times. In contrast to '--cold-cache', this does not \
+                     require any special privileges. Only supported on Linux.",
+                ),
+        )
+        .arg(
+            Arg::new("cpu-affinity")
+                .long("cpu-affinity")
+                .action(ArgAction::Set)
+                .value_name("CPUS")
+                .help(
+                    "Pin the benchmarked commands to the given CPUs, e.g. '2,3' or '0-3'. This is \
+                     applied directly before the command is executed, without an additional \
+                     process like 'taskset'. Only supported on Linux.",
+                ),
+        )
+        .arg(
+            Arg::new("nice")
+                .long("nice")
+                .action(ArgAction::Set)
+                .allow_hyphen_values(true)
+                .value_name("NICENESS")
+                .help(
+                    "Run the benchmarked commands with the given niceness (-20 to 19, lower \
+                     values mean higher priority). Negative values require elevated privileges. \
+                     Only supported on Linux.",
+                ),
+        )
+        .arg(
+            Arg::new("sched-fifo")
+                .long("sched-fifo")
+                .action(ArgAction::Set)
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("1")
+                .value_name("PRIORITY")
+                .help(
+                    "Run the benchmarked commands with the real-time scheduling policy SCHED_FIFO \
+                     and the given priority (1 to 99, default: 1). This usually requires elevated \
+                     privileges. Only supported on Linux.",
+                ),
+        )
+        .arg(
+            Arg::new("strict-environment")
+                .long("strict-environment")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Refuse to run the benchmarks if the system is likely to produce noisy \
+                     measurements. On Linux, hyperfine always checks the CPU frequency scaling \
+                     governor, turbo boost and the system load before benchmarking, and shows a \
+                     warning for every problem found. With this option, it additionally checks \
+                     for swap activity and address space layout randomization (which is reported, \
+                     but does not prevent the benchmarks from running).",
+                ),
+        )
+        .arg(
+            Arg::new("outliers")
+                .long("outliers")
+                .action(ArgAction::Set)
+                .value_name("POLICY")
+                .value_parser(["keep", "remove", "winsorize"])
+                .help(
+                    "Specify how statistical outliers in the run times are handled. Outliers are \
+                     detected via their modified Z-score (based on the median absolute deviation):\n  \
+                       * 'keep' (default): keep all measurements, but show a warning\n  \
+                       * 'remove': exclude outliers from the summary statistics\n  \
+                       * 'winsorize': clamp outliers to the range of the remaining measurements\n\
+                     The individual run times in the JSON export always contain all measurements. \
+                     The indices of removed or clamped runs are listed in the 'outliers' field, \
+                     and the policy in the 'outlier_policy' field.",
+                ),
+        )
         .arg(
             Arg::new("time-unit")
                 .long("time-unit")
@@ -279,6 +457,17 @@ fn build_command() -> Command {
                        If the option is not given, the time unit is determined automatically. \
                        This option affects the standard output as well as all export formats except for CSV and JSON."),
         )
+        .arg(
+            Arg::new("percentiles")
+                .long("percentiles")
+                .action(ArgAction::Set)
+                .value_name("LIST")
+                .help("Comma-separated list of percentiles of the run times that should be \
+                       computed for each command (default: 50,90,95,99). The percentiles are \
+                       included in the CSV and JSON exports and can be selected as columns \
+                       of the markup tables via '--export-columns'. Use an empty list to \
+                       disable them."),
+        )
         .arg(
             Arg::new("export-asciidoc")
                 .long("export-asciidoc")
@@ -298,6 +487,17 @@ fn build_command() -> Command {
                        the timing results for each individual run, use the JSON export format. \
                        The output time unit is always seconds."),
         )
+        .arg(
+            Arg::new("export-html")
+                .long("export-html")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Export a self-contained HTML report to the given FILE. The report contains \
+                       the summary table, a relative speed comparison, histograms of the individual \
+                       run times and the raw JSON data. The output time unit can be changed using \
+                       the --time-unit option."),
+        )
         .arg(
             Arg::new("export-json")
                 .long("export-json")
@@ -307,6 +507,42 @@ fn build_command() -> Command {
                 .help("Export the timing summary statistics and timings of individual runs as JSON to the given FILE. \
                        The output time unit is always seconds"),
         )
+        .arg(
+            Arg::new("export-junit")
+                .long("export-junit")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Export the results as JUnit XML report to the given FILE, for the test reporting \
+                       of CI systems. Every command is a test case with its mean time as duration and \
+                       the summary statistics (in seconds) and parameters as properties. Commands with \
+                       a non-zero exit code are reported as failures."),
+        )
+        .arg(
+            Arg::new("junit-threshold")
+                .long("junit-threshold")
+                .action(ArgAction::Set)
+                .value_name("FACTOR")
+                .requires("export-junit")
+                .help("Also report commands as failures in the JUnit export if they are more than \
+                       FACTOR times slower than the reference (the '--reference' command or the \
+                       fastest command), e.g. '--junit-threshold 1.1'."),
+        )
+        .arg(
+            Arg::new("export-ndjson")
+                .long("export-ndjson")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Stream the progress of the benchmark session to the given FILE as newline-delimited \
+                       JSON, with one event per line (session start, shell spawning time calibration, \
+                       benchmark start, every warmup and benchmark run, benchmark end with the summary \
+                       statistics, and session end). Every event has an 'event' field with its type and a \
+                       'time' field with the seconds since the start of the session. The events are written \
+                       as soon as they happen. The 'session_end' event is also written if a benchmark fails \
+                       (with an additional 'error' field). Use '-' to write the events to stdout (which \
+                       disables the terminal output, like '--style=none')."),
+        )
         .arg(
             Arg::new("export-markdown")
                 .long("export-markdown")
@@ -316,6 +552,17 @@ fn build_command() -> Command {
                 .help("Export the timing summary statistics as a Markdown table to the given FILE. \
                        The output time unit can be changed using the --time-unit option."),
         )
+        .arg(
+            Arg::new("export-openmetrics")
+                .long("export-openmetrics")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Export the summary statistics as gauges in the OpenMetrics (Prometheus) text \
+                       format to the given FILE, e.g. for the textfile collector of the node exporter \
+                       or for a Pushgateway. The samples are labeled with the command and with the \
+                       values of all parameters. The output time unit is always seconds."),
+        )
         .arg(
             Arg::new("export-orgmode")
                 .long("export-orgmode")
@@ -325,6 +572,94 @@ fn build_command() -> Command {
                 .help("Export the timing summary statistics as an Emacs org-mode table to the given FILE. \
                        The output time unit can be changed using the --time-unit option."),
         )
+        .arg(
+            Arg::new("export-latex")
+                .long("export-latex")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Export the timing summary statistics as a LaTeX table (using the booktabs \
+                       package) to the given FILE. The output time unit can be changed using the \
+                       --time-unit option."),
+        )
+        .arg(
+            Arg::new("export-rst")
+                .long("export-rst")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Export the timing summary statistics as a reStructuredText grid table to the \
+                       given FILE. The output time unit can be changed using the --time-unit option."),
+        )
+        .arg(
+            Arg::new("export-github-benchmark")
+                .long("export-github-benchmark")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Export the results in the 'customSmallerIsBetter' JSON format of \
+                       github-action-benchmark to the given FILE. The value is given by the \
+                       '--statistic' and the range by the standard deviation. Benchmarks with the \
+                       same name are distinguished by their number. The output time unit can be \
+                       changed using the --time-unit option."),
+        )
+        .arg(
+            Arg::new("export-bencher")
+                .long("export-bencher")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Export the results in the Bencher Metric Format (JSON) to the given FILE, with \
+                       the '--statistic' as 'latency' measure in nanoseconds. The lower and upper \
+                       bounds are given by the standard deviation (or by the minimum and maximum \
+                       for a single run)."),
+        )
+        .arg(
+            Arg::new("export-metadata")
+                .long("export-metadata")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Include a table with information about the system (host, operating system, \
+                     CPU, memory, CPU governor, load average) and the hyperfine invocation at the \
+                     top of the AsciiDoc, Markdown, org-mode, LaTeX and reStructuredText exports. \
+                     This information is always included in the JSON and HTML exports.",
+                ),
+        )
+        .arg(
+            Arg::new("export-phase-times")
+                .long("export-phase-times")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Include the times of the warmup runs, of every '--prepare' and '--conclude' \
+                     command and of the '--setup' and '--cleanup' commands as separate arrays \
+                     ('phase_times') in the JSON export. This can help to diagnose cache effects.",
+                ),
+        )
+        .arg(
+            Arg::new("export-columns")
+                .long("export-columns")
+                .action(ArgAction::Set)
+                .value_name("COLUMNS")
+                .help("Comma-separated list of columns for the markup table exports (Markdown, \
+                       AsciiDoc, org-mode, LaTeX, reStructuredText). Possible columns are 'command', \
+                       'mean', 'median', 'min', 'max', 'user', 'system', 'memory' (maximum memory \
+                       usage), 'relative', percentiles like 'p95' or 'p99.9' and parameter values \
+                       like 'parameter_threads' (for a parameter named 'threads'). If this option \
+                       is given, a table with the selected columns is also shown in the terminal. \
+                       Default: command,mean,min,max,relative"),
+        )
+        .arg(
+            Arg::new("from-json")
+                .long("from-json")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .conflicts_with("command")
+                .help("Do not run any benchmarks, but read the results from a FILE that has been \
+                       written with '--export-json'. The results are shown in the comparison and \
+                       can be exported to other formats, e.g. \
+                       'hyperfine --from-json results.json --export-markdown results.md'."),
+        )
         .arg(
             Arg::new("show-output")
                 .long("show-output")
@@ -357,13 +692,105 @@ fn build_command() -> Command {
                      \n  \
                        inherit:  Don't redirect the output at all (same as '--show-output').\n\
                      \n  \
-                       <FILE>:   Write the output to the given file.\n\n\
+                       <FILE>:   Write the output to the given file.\n\
+                     \n  \
+                       <TEMPLATE>: Write the output (stdout and stderr) of every run to a separate \
+                                 file. The path can contain the placeholders {command_index} (starting \
+                                 at 1), {command_name}, {iteration} (e.g. '3' or 'warmup-0'), {stream} \
+                                 ('stdout' or 'stderr') and parameters like {size}. Without {stream}, both \
+                                 streams are written to the same file. Example: \
+                                 --output='logs/{command_index}/{iteration}.{stream}'\n\n\
                     This option can be specified once for all commands or multiple times, once for \
-                    each command. Note: If you want to log the output of each and every iteration, \
-                    you can use a shell redirection and the '$HYPERFINE_ITERATION' environment variable:\n    \
+                    each command. Note: Instead of a template, you can also use a shell redirection \
+                    and the '$HYPERFINE_ITERATION' environment variable to log the output of each \
+                    and every iteration:\n    \
                     hyperfine 'my-command > output-${HYPERFINE_ITERATION}.log'\n\n",
                 ),
         )
+        .arg(
+            Arg::new("stdout")
+                .long("stdout")
+                .conflicts_with_all(["show-output", "output"])
+                .action(ArgAction::Append)
+                .value_name("WHERE")
+                .help(
+                    "Control where the standard output of the benchmark is redirected, independently \
+                     of its standard error (see '--stderr'). <WHERE> can be 'null' (the default), \
+                     'pipe', 'inherit' or 'file:<PATH>'. This option can be specified once for all \
+                     commands or multiple times, once for each command.",
+                ),
+        )
+        .arg(
+            Arg::new("stderr")
+                .long("stderr")
+                .conflicts_with_all(["show-output", "output"])
+                .action(ArgAction::Append)
+                .value_name("WHERE")
+                .help(
+                    "Control where the standard error of the benchmark is redirected, e.g. \
+                     '--stdout=null --stderr=pipe' for tools that write their bulk output to \
+                     stderr. The possible values are the same as for '--stdout'.",
+                ),
+        )
+        .arg(
+            Arg::new("expect-output")
+                .long("expect-output")
+                .action(ArgAction::Append)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .conflicts_with("expect-output-hash")
+                .help(
+                    "Check that the standard output of the benchmarked commands is identical to the \
+                     content of FILE, in order to make sure that a fast command is not simply broken. \
+                     The output is fed through a pipe and hashed while it is being checked. This option \
+                     can be specified once for all commands or multiple times, once for each command.",
+                ),
+        )
+        .arg(
+            Arg::new("expect-output-hash")
+                .long("expect-output-hash")
+                .action(ArgAction::Append)
+                .value_name("SHA256")
+                .help(
+                    "Like '--expect-output', but compare the output against the given SHA-256 hash \
+                     (e.g. from 'sha256sum') instead of the content of a file.",
+                ),
+        )
+        .arg(
+            Arg::new("expect-identical-output")
+                .long("expect-identical-output")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Check that all benchmarked commands (including a potential reference) produce \
+                     the same standard output.",
+                ),
+        )
+        .arg(
+            Arg::new("check-output")
+                .long("check-output")
+                .action(ArgAction::Set)
+                .value_name("RUNS")
+                .value_parser(["first", "all"])
+                .help(
+                    "Which runs to check when using '--expect-output', '--expect-output-hash' or \
+                     '--expect-identical-output':\n  \
+                       * 'first' (default): only check the output of each command once, in an\n    \
+                         additional run before the benchmark that is not part of the measurements.\n  \
+                       * 'all': check the output of every benchmark run. Without an expected\n    \
+                         output, all runs are required to produce the same output as the first one.",
+                ),
+        )
+        .arg(
+            Arg::new("output-mismatch")
+                .long("output-mismatch")
+                .action(ArgAction::Set)
+                .value_name("ACTION")
+                .value_parser(["fail", "warn"])
+                .help(
+                    "What to do if the output of a command does not match the expectation: \
+                     'fail' (default) aborts the benchmark, 'warn' only shows a warning.",
+                ),
+        )
         .arg(
             Arg::new("input")
                 .long("input")
@@ -376,7 +803,18 @@ fn build_command() -> Command {
                        \n  \
                          null:     Read from /dev/null (the default).\n\
                        \n  \
-                         <FILE>:   Read the input from the given file."),
+                         <FILE>:   Read the input from the given file.\n\
+                       \n  \
+                         <DIR>:    Cycle through the files in the given directory (in lexicographic\n             \
+                                   order), using a different file for each iteration.\n\
+                       \n  \
+                         template:<TEMPLATE>: Read the input from a file whose path depends on the\n             \
+                                   iteration or on the parameters, e.g.\n             \
+                                   'template:inputs/{size}/$HYPERFINE_ITERATION.txt'. Commands that are not\n             \
+                                   benchmarked (like '--prepare') read from /dev/null in this case.\n\
+                       \n\
+                       Prefix any of the above with 'pipe:' to stream the file content through a pipe \
+                       instead of passing a (seekable) file descriptor, e.g. '--input pipe:data.txt'."),
         )
         .arg(
             Arg::new("command-name")
@@ -1,5 +1,6 @@
 use std::collections::BTreeMap;
 use std::fmt;
+use std::path::Path;
 use std::str::FromStr;
 
 use crate::parameter::tokenize::tokenize;
@@ -14,7 +15,7 @@ use crate::{
 
 use clap::{parser::ValuesRef, ArgMatches};
 
-use anyhow::{bail, Context, Result};
+use anyhow::{bail, ensure, Context, Result};
 use rust_decimal::Decimal;
 
 /// A command that should be benchmarked.
@@ -28,6 +29,16 @@ pub struct Command<'a> {
 
     /// Zero or more parameter values.
     parameters: Vec<ParameterNameAndValue<'a>>,
+
+    /// Additional environment variables, each given as whitespace-separated 'KEY=VALUE'
+    /// pairs (without parameter substitution)
+    environment: &'a [String],
+
+    /// The working directory (without parameter substitution)
+    working_directory: Option<&'a str>,
+
+    /// Names of the parameters that are passed as environment variables
+    exported_parameters: Vec<&'a str>,
 }
 
 impl<'a> Command<'a> {
@@ -36,6 +47,9 @@ impl<'a> Command<'a> {
             name,
             expression,
             parameters: Vec::new(),
+            environment: &[],
+            working_directory: None,
+            exported_parameters: Vec::new(),
         }
     }
 
@@ -48,6 +62,34 @@ impl<'a> Command<'a> {
             name,
             expression,
             parameters: parameters.into_iter().collect(),
+            environment: &[],
+            working_directory: None,
+            exported_parameters: Vec::new(),
+        }
+    }
+
+    /// Run this command with additional environment variables (given as whitespace-separated
+    /// 'KEY=VALUE' pairs) and/or in a different working directory. Both may contain parameters.
+    pub fn with_environment(
+        self,
+        environment: &'a [String],
+        working_directory: Option<&'a str>,
+    ) -> Command<'a> {
+        Command {
+            environment,
+            working_directory,
+            ..self
+        }
+    }
+
+    /// Run this command (like a '--prepare' command) with the environment variables, exported
+    /// parameters and working directory of the given benchmarked command.
+    pub fn with_environment_of(self, command: &Command<'a>) -> Command<'a> {
+        Command {
+            environment: command.environment,
+            working_directory: command.working_directory,
+            exported_parameters: command.exported_parameters.clone(),
+            ..self
         }
     }
 
@@ -87,12 +129,50 @@ impl<'a> Command<'a> {
         if let Some(program_name) = tokens.next() {
             let mut command_builder = std::process::Command::new(program_name);
             command_builder.args(tokens);
+            self.configure_process(&mut command_builder)?;
             Ok(command_builder)
         } else {
             bail!("Can not execute empty command")
         }
     }
 
+    /// The additional environment variables: exported parameters, followed by the
+    /// variables from '--env' (with parameters substituted)
+    pub fn get_environment(&self) -> Result<Vec<(String, String)>> {
+        let mut environment: Vec<(String, String)> = self
+            .parameters
+            .iter()
+            .filter(|(name, _)| self.exported_parameters.contains(name))
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        for env in self.environment {
+            environment.extend(parse_environment(&self.replace_parameters_in(env))?);
+        }
+
+        Ok(environment)
+    }
+
+    /// The working directory (with parameters substituted)
+    pub fn get_working_directory(&self) -> Option<String> {
+        self.working_directory
+            .map(|dir| self.replace_parameters_in(dir))
+    }
+
+    /// Apply the environment variables and the working directory of this command
+    /// to the given process builder.
+    pub fn configure_process(&self, command_builder: &mut std::process::Command) -> Result<()> {
+        command_builder.envs(self.get_environment()?);
+        if let Some(dir) = self.get_working_directory() {
+            ensure!(
+                Path::new(&dir).is_dir(),
+                "The working directory '{dir}' does not exist"
+            );
+            command_builder.current_dir(dir);
+        }
+        Ok(())
+    }
+
     pub fn get_parameters(&self) -> &[(&'a str, ParameterValue)] {
         &self.parameters
     }
@@ -103,7 +183,7 @@ impl<'a> Command<'a> {
             .filter(move |(parameter, _)| !self.expression.contains(&format!("{{{parameter}}}")))
     }
 
-    fn replace_parameters_in(&self, original: &str) -> String {
+    pub fn replace_parameters_in(&self, original: &str) -> String {
         let mut result = String::new();
         let mut replacements = BTreeMap::<String, String>::new();
         for (param_name, param_value) in &self.parameters {
@@ -130,11 +210,47 @@ impl<'a> Command<'a> {
     }
 }
 
+/// Parse whitespace-separated 'KEY=VALUE' pairs (with shell-like quoting) as given to '--env'
+pub fn parse_environment(environment: &str) -> Result<Vec<(String, String)>> {
+    shell_words::split(environment)
+        .with_context(|| format!("Failed to parse '--env' value '{environment}'"))?
+        .into_iter()
+        .map(|assignment| match assignment.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+            _ => bail!(
+                "Invalid environment variable assignment '{assignment}' in '--env'. \
+                 Expected KEY=VALUE"
+            ),
+        })
+        .collect()
+}
+
 /// A collection of commands that should be benchmarked
 pub struct Commands<'a>(Vec<Command<'a>>);
 
 impl<'a> Commands<'a> {
     pub fn from_cli_arguments(matches: &'a ArgMatches) -> Result<Commands<'a>> {
+        let mut commands = Self::build_commands(matches)?;
+
+        if let Some(exported) = matches.get_many::<String>("parameter-env") {
+            let exported: Vec<&str> = exported.map(|v| v.as_str()).collect();
+            for command in &mut commands.0 {
+                for name in &exported {
+                    if !command.parameters.iter().any(|(param, _)| param == name) {
+                        bail!(
+                            "Unknown parameter '{name}' in '--parameter-env'. It has to be defined \
+                             via '--parameter-scan' or '--parameter-list'."
+                        );
+                    }
+                }
+                command.exported_parameters = exported.clone();
+            }
+        }
+
+        Ok(commands)
+    }
+
+    fn build_commands(matches: &'a ArgMatches) -> Result<Commands<'a>> {
         let command_names = matches.get_many::<String>("command-name");
         let command_strings = matches
             .get_many::<String>("command")
@@ -371,6 +487,97 @@ fn test_get_command_line_nonoverlapping() {
     assert_eq!(cmd.get_command_line(), "echo {bar} baz quux");
 }
 
+#[test]
+fn test_parse_environment() {
+    assert_eq!(parse_environment("").unwrap(), vec![]);
+    assert_eq!(
+        parse_environment("RUST_LOG=debug  MSG='a b=c' EMPTY=").unwrap(),
+        vec![
+            ("RUST_LOG".into(), "debug".into()),
+            ("MSG".into(), "a b=c".into()),
+            ("EMPTY".into(), "".into())
+        ]
+    );
+    assert!(parse_environment("RUST_LOG").is_err());
+    assert!(parse_environment("=value").is_err());
+    assert!(parse_environment("KEY='unterminated").is_err());
+}
+
+#[test]
+fn test_get_parameterized_environment() {
+    let environment = vec!["NUM_THREADS={threads}".to_string(), "MODE=fast".to_string()];
+    let cmd = Command::new_parametrized(
+        None,
+        "echo",
+        vec![
+            ("threads", ParameterValue::Text("4".into())),
+            ("dir", ParameterValue::Text("/tmp".into())),
+        ],
+    )
+    .with_environment(&environment, Some("{dir}"));
+
+    assert_eq!(
+        cmd.get_environment().unwrap(),
+        vec![
+            ("NUM_THREADS".into(), "4".into()),
+            ("MODE".into(), "fast".into())
+        ]
+    );
+    assert_eq!(cmd.get_working_directory().as_deref(), Some("/tmp"));
+    assert_eq!(
+        Command::new(None, "echo").get_environment().unwrap(),
+        vec![]
+    );
+}
+
+#[test]
+fn test_build_commands_with_exported_parameters() {
+    use crate::cli::get_cli_arguments;
+
+    let matches = get_cli_arguments(vec![
+        "hyperfine",
+        "-P",
+        "NUM_THREADS",
+        "1",
+        "2",
+        "--parameter-env",
+        "NUM_THREADS",
+        "--env",
+        "MODE=fast",
+        "echo",
+    ]);
+    let commands = Commands::from_cli_arguments(&matches).unwrap().0;
+
+    assert_eq!(commands.len(), 2);
+    assert_eq!(
+        commands[1]
+            .clone()
+            .with_environment(&["MODE=fast".to_string()], None)
+            .get_environment()
+            .unwrap(),
+        vec![
+            ("NUM_THREADS".into(), "2".into()),
+            ("MODE".into(), "fast".into())
+        ]
+    );
+    assert_eq!(
+        commands[1].get_name_with_unused_parameters(),
+        "echo (NUM_THREADS = 2)"
+    );
+
+    let matches = get_cli_arguments(vec![
+        "hyperfine",
+        "-P",
+        "threads",
+        "1",
+        "2",
+        "--parameter-env",
+        "THREADS",
+        "echo",
+    ]);
+    assert!(Commands::from_cli_arguments(&matches).is_err());
+}
+
 #[test]
 fn test_get_parameterized_command_name() {
     let cmd = Command::new_parametrized(
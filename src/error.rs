@@ -47,12 +47,44 @@ pub enum OptionsError<'a> {
     IntParsingError(&'a str, ParseIntError),
     #[error("Could not read numeric floating point argument to '--{0}': {1}")]
     FloatParsingError(&'a str, ParseFloatError),
+    #[error("The factor for '--junit-threshold' must be at least 1 (e.g. 1.1 to allow a slowdown of 10%)")]
+    InvalidJunitThreshold,
     #[error("An empty command has been specified for the '--shell <command>' option")]
     EmptyShell,
     #[error("Failed to parse '--shell <command>' expression as command line: {0}")]
     ShellParseError(shell_words::ParseError),
     #[error("Unknown output policy '{0}'. Use './{0}' to output to a file named '{0}'.")]
     UnknownOutputPolicy(String),
+    #[error("Unknown output policy '{0}'. Use 'null', 'pipe', 'inherit' or 'file:<PATH>'.")]
+    UnknownStreamPolicy(String),
+    #[error("The '--stdout' and '--stderr' options have to be provided the same number of times (or just once)")]
+    StreamPolicyCountMismatch,
+    #[error("Invalid percentile '{0}'. Percentiles have to be numbers between 0 and 100")]
+    InvalidPercentile(String),
+    #[error("Unknown statistic '{0}'. Possible values: mean, median, min, p<N> (e.g. p95)")]
+    UnknownStatistic(String),
+    #[error("Unknown export column '{0}'. Possible values: command, mean, median, min, max, user, system, memory, relative, p<N> (e.g. p95), parameter_<NAME>")]
+    UnknownExportColumn(String),
+    #[error(
+        "Invalid CPU list '{0}'. Use a comma-separated list of CPU numbers or ranges like '0,2-3'"
+    )]
+    InvalidCpuList(String),
+    #[error("Invalid niceness {0}. The value has to be between -20 and 19")]
+    InvalidNiceness(i32),
+    #[error("Invalid real-time priority {0}. The value has to be between 1 and 99")]
+    InvalidRealtimePriority(i32),
+    #[error("The '--{0}' option is only supported on Linux")]
+    LinuxOnlyOption(&'static str),
+    #[error("The file '{0}' specified as '--cold-cache-file' does not exist")]
+    ColdCacheFileDoesNotExist(String),
     #[error("The file '{0}' specified as '--input' does not exist")]
     StdinDataFileDoesNotExist(String),
+    #[error("The directory '{0}' specified as '--input' does not contain any files")]
+    EmptyInputDirectory(String),
+    #[error("The file '{0}' specified as '--expect-output' could not be read")]
+    ExpectedOutputFileNotReadable(String),
+    #[error("Invalid SHA-256 hash '{0}'. Expected 64 hexadecimal characters")]
+    InvalidOutputHash(String),
+    #[error("The events of '--export-ndjson -' are written to stdout, which can not be combined with '--style {0}'. Use '--style none' or write the events to a file")]
+    NdjsonOnStdoutWithOutputStyle(String),
 }
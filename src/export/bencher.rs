@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+
+use serde::*;
+use serde_json::to_vec_pretty;
+
+use super::markup::Column;
+use super::{benchmark_names, Exporter, Metadata};
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::options::{SortOrder, SummaryStatistic};
+use crate::util::units::{Scalar, Unit};
+
+use anyhow::Result;
+
+/// A value of a measure in the Bencher Metric Format
+#[derive(Serialize, Debug)]
+struct Metric {
+    value: Scalar,
+    lower_value: Scalar,
+    upper_value: Scalar,
+}
+
+/// Exports the results in the Bencher Metric Format (BMF). Every benchmarked command is
+/// reported with its run time as 'latency' measure (in nanoseconds). The bounds are given
+/// by the standard deviation around the selected statistic, or by the minimum and maximum
+/// if only a single run has been performed. Benchmarks with the same name are distinguished
+/// by their number.
+#[derive(Default)]
+pub struct BencherExporter {}
+
+impl Exporter for BencherExporter {
+    fn serialize(
+        &self,
+        results: &[BenchmarkResult],
+        _unit: Option<Unit>,
+        _sort_order: SortOrder,
+        statistic: SummaryStatistic,
+        _columns: &[Column],
+        _metadata: Option<&Metadata>,
+    ) -> Result<Vec<u8>> {
+        let to_nanoseconds = |seconds: Scalar| seconds * 1e9;
+
+        let benchmarks: BTreeMap<_, _> = results
+            .iter()
+            .zip(benchmark_names(results))
+            .map(|(result, name)| {
+                let value = result.statistic(statistic);
+                let (lower_value, upper_value) = match result.stddev {
+                    Some(stddev) => (value - stddev, value + stddev),
+                    None => (result.min, result.max),
+                };
+
+                let mut measures = BTreeMap::new();
+                measures.insert(
+                    "latency",
+                    Metric {
+                        value: to_nanoseconds(value),
+                        // Run times can not be negative
+                        lower_value: to_nanoseconds(lower_value.max(0.0)),
+                        upper_value: to_nanoseconds(upper_value),
+                    },
+                );
+                (name, measures)
+            })
+            .collect();
+
+        let mut output = to_vec_pretty(&benchmarks)?;
+        output.push(b'\n');
+
+        Ok(output)
+    }
+}
+
+#[test]
+fn test_bencher_export() {
+    let results = [
+        BenchmarkResult {
+            command: String::from("sleep 0.5"),
+            command_with_unused_parameters: String::from("sleep 0.5"),
+            mean: 0.5,
+            stddev: Some(0.75),
+            median: 0.5,
+            min: 0.125,
+            max: 1.0,
+            ..Default::default()
+        },
+        BenchmarkResult {
+            command: String::from("sleep 2"),
+            command_with_unused_parameters: String::from("sleep 2 (size = 5)"),
+            mean: 2.0,
+            stddev: None,
+            median: 2.0,
+            min: 2.0,
+            max: 2.0,
+            parameters: vec![(String::from("size"), String::from("5"))]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        },
+    ];
+
+    let output = BencherExporter::default()
+        .serialize(
+            &results,
+            None,
+            SortOrder::Command,
+            SummaryStatistic::Mean,
+            &[],
+            None,
+        )
+        .unwrap();
+
+    let benchmarks: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        benchmarks,
+        serde_json::json!({
+            "sleep 0.5": {
+                "latency": {
+                    "value": 5e8,
+                    "lower_value": 0.0,
+                    "upper_value": 1.25e9,
+                },
+            },
+            "sleep 2 (size = 5)": {
+                "latency": {
+                    "value": 2e9,
+                    "lower_value": 2e9,
+                    "upper_value": 2e9,
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn test_bencher_export_name_collisions() {
+    let result = |command: &str, mean: Scalar| BenchmarkResult {
+        command: String::from(command),
+        command_with_unused_parameters: String::from(command),
+        mean,
+        min: mean,
+        max: mean,
+        ..Default::default()
+    };
+    let results = [
+        result("build", 1.0),
+        result("test", 2.0),
+        result("build", 3.0),
+    ];
+
+    let output = BencherExporter::default()
+        .serialize(
+            &results,
+            None,
+            SortOrder::Command,
+            SummaryStatistic::Mean,
+            &[],
+            None,
+        )
+        .unwrap();
+
+    let benchmarks: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let names: Vec<_> = benchmarks.as_object().unwrap().keys().collect();
+    assert_eq!(names, ["build #1", "build #3", "test"]);
+    assert_eq!(benchmarks["build #1"]["latency"]["value"], 1e9);
+    assert_eq!(benchmarks["build #3"]["latency"]["value"], 3e9);
+}
@@ -2,9 +2,10 @@ use std::borrow::Cow;
 
 use csv::WriterBuilder;
 
-use super::Exporter;
-use crate::benchmark::benchmark_result::BenchmarkResult;
-use crate::options::SortOrder;
+use super::markup::Column;
+use super::{Exporter, Metadata};
+use crate::benchmark::benchmark_result::{BenchmarkResult, Percentiles};
+use crate::options::{SortOrder, SummaryStatistic};
 use crate::util::units::Unit;
 
 use anyhow::Result;
@@ -18,6 +19,9 @@ impl Exporter for CsvExporter {
         results: &[BenchmarkResult],
         _unit: Option<Unit>,
         _sort_order: SortOrder,
+        _statistic: SummaryStatistic,
+        _columns: &[Column],
+        _metadata: Option<&Metadata>,
     ) -> Result<Vec<u8>> {
         let mut writer = WriterBuilder::new().from_writer(vec![]);
 
@@ -33,6 +37,10 @@ impl Exporter for CsvExporter {
                 for param_name in res.parameters.keys() {
                     headers.push(Cow::Owned(format!("parameter_{param_name}").into_bytes()));
                 }
+                // Appended after all other columns, to keep the positions of existing columns
+                for (p, _) in res.percentiles.iter() {
+                    headers.push(Cow::Owned(Percentiles::name(*p).into_bytes()));
+                }
             }
             writer.write_record(headers)?;
         }
@@ -53,6 +61,9 @@ impl Exporter for CsvExporter {
             for v in res.parameters.values() {
                 fields.push(Cow::Borrowed(v.as_bytes()))
             }
+            for (_, value) in res.percentiles.iter() {
+                fields.push(Cow::Owned(value.to_string().into_bytes()))
+            }
             writer.write_record(fields)?;
         }
 
@@ -76,8 +87,8 @@ fn test_csv() {
             system: 4.0,
             min: 5.0,
             max: 6.0,
+            percentiles: Percentiles(vec![(50.0, 8.0), (99.0, 8.98)]),
             times: Some(vec![7.0, 8.0, 9.0]),
-            memory_usage_byte: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
             parameters: {
                 let mut params = BTreeMap::new();
@@ -85,6 +96,7 @@ fn test_csv() {
                 params.insert("bar".into(), "two".into());
                 params
             },
+            ..Default::default()
         },
         BenchmarkResult {
             command: String::from("command_b"),
@@ -96,8 +108,8 @@ fn test_csv() {
             system: 14.0,
             min: 15.0,
             max: 16.5,
+            percentiles: Percentiles(vec![(50.0, 18.0), (99.0, 18.98)]),
             times: Some(vec![17.0, 18.0, 19.0]),
-            memory_usage_byte: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
             parameters: {
                 let mut params = BTreeMap::new();
@@ -105,19 +117,27 @@ fn test_csv() {
                 params.insert("bar".into(), "seven".into());
                 params
             },
+            ..Default::default()
         },
     ];
 
     let actual = String::from_utf8(
         exporter
-            .serialize(&results, Some(Unit::Second), SortOrder::Command)
+            .serialize(
+                &results,
+                Some(Unit::Second),
+                SortOrder::Command,
+                SummaryStatistic::Mean,
+                &Column::DEFAULT,
+                None,
+            )
             .unwrap(),
     )
     .unwrap();
 
     insta::assert_snapshot!(actual, @r#"
-    command,mean,stddev,median,user,system,min,max,parameter_bar,parameter_foo
-    command_a,1,2,1,3,4,5,6,two,one
-    command_b,11,12,11,13,14,15,16.5,seven,one
+    command,mean,stddev,median,user,system,min,max,parameter_bar,parameter_foo,p50,p99
+    command_a,1,2,1,3,4,5,6,two,one,8,8.98
+    command_b,11,12,11,13,14,15,16.5,seven,one,18,18.98
     "#);
 }
@@ -0,0 +1,148 @@
+use serde::*;
+use serde_json::to_vec_pretty;
+
+use super::markup::{determine_unit_from_results, Column};
+use super::{benchmark_names, Exporter, Metadata};
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::options::{SortOrder, SummaryStatistic};
+use crate::util::units::{Scalar, Unit};
+
+use anyhow::Result;
+
+/// A single entry in the "customSmallerIsBetter" format of github-action-benchmark
+#[derive(Serialize, Debug)]
+struct Entry {
+    name: String,
+    unit: String,
+    value: Scalar,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    range: Option<String>,
+    extra: String,
+}
+
+/// Exports the results in the "customSmallerIsBetter" JSON format of
+/// github-action-benchmark, with one entry per benchmarked command.
+#[derive(Default)]
+pub struct GithubBenchmarkExporter {}
+
+impl Exporter for GithubBenchmarkExporter {
+    fn serialize(
+        &self,
+        results: &[BenchmarkResult],
+        unit: Option<Unit>,
+        _sort_order: SortOrder,
+        statistic: SummaryStatistic,
+        _columns: &[Column],
+        _metadata: Option<&Metadata>,
+    ) -> Result<Vec<u8>> {
+        let unit = unit.unwrap_or_else(|| determine_unit_from_results(results));
+
+        let entries: Vec<_> = results
+            .iter()
+            .zip(benchmark_names(results))
+            .map(|(result, name)| {
+                let mut extra = vec![
+                    format!("{statistic} of {} runs", result.exit_codes.len()),
+                    format!(
+                        "min: {} {unit_name}, max: {} {unit_name}",
+                        unit.format(result.min),
+                        unit.format(result.max),
+                        unit_name = unit.short_name()
+                    ),
+                ];
+                extra.extend(
+                    result
+                        .parameters
+                        .iter()
+                        .map(|(name, value)| format!("{name} = {value}")),
+                );
+
+                Entry {
+                    name,
+                    unit: unit.short_name(),
+                    value: unit.convert(result.statistic(statistic)),
+                    range: result
+                        .stddev
+                        .map(|stddev| format!("± {}", unit.format(stddev))),
+                    extra: extra.join("\n"),
+                }
+            })
+            .collect();
+
+        let mut output = to_vec_pretty(&entries)?;
+        output.push(b'\n');
+
+        Ok(output)
+    }
+}
+
+#[test]
+fn test_github_benchmark_export() {
+    let results = [BenchmarkResult {
+        command: String::from("sleep 0.1"),
+        command_with_unused_parameters: String::from("sleep 0.1 (size = 5)"),
+        mean: 0.105,
+        stddev: Some(0.0016),
+        median: 0.125,
+        min: 0.1023,
+        max: 0.1375,
+        exit_codes: vec![Some(0); 10],
+        parameters: vec![(String::from("size"), String::from("5"))]
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    }];
+
+    let output = GithubBenchmarkExporter::default()
+        .serialize(
+            &results,
+            None,
+            SortOrder::Command,
+            SummaryStatistic::Median,
+            &[],
+            None,
+        )
+        .unwrap();
+
+    let entries: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        entries,
+        serde_json::json!([{
+            "name": "sleep 0.1 (size = 5)",
+            "unit": "ms",
+            "value": 125.0,
+            "range": "± 1.6",
+            "extra": "median of 10 runs\nmin: 102.3 ms, max: 137.5 ms\nsize = 5",
+        }])
+    );
+}
+
+#[test]
+fn test_github_benchmark_export_name_collisions() {
+    let result = |command: &str| BenchmarkResult {
+        command: String::from(command),
+        command_with_unused_parameters: String::from(command),
+        ..Default::default()
+    };
+    let results = [result("build"), result("test"), result("build")];
+
+    let output = GithubBenchmarkExporter::default()
+        .serialize(
+            &results,
+            None,
+            SortOrder::Command,
+            SummaryStatistic::Mean,
+            &[],
+            None,
+        )
+        .unwrap();
+
+    let entries: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let names: Vec<_> = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["build #1", "test", "build #3"]);
+}
@@ -0,0 +1,464 @@
+use std::fmt::Write as _;
+
+use super::json::JsonExporter;
+use super::markup::determine_unit_from_results;
+use super::markup::Column;
+use super::{Exporter, Metadata};
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::benchmark::relative_speed::{self, BenchmarkResultWithRelativeSpeed};
+use crate::options::{SortOrder, SummaryStatistic};
+use crate::output::format::format_duration_value;
+use crate::output::histogram::{bin_count, bin_counts};
+use crate::util::min_max::{max, min};
+use crate::util::units::{Second, Unit};
+
+use anyhow::Result;
+use clap::crate_version;
+
+/// Dimensions of the inline SVG charts (in pixels)
+const CHART_WIDTH: f64 = 480.0;
+const HISTOGRAM_HEIGHT: f64 = 160.0;
+const BAR_HEIGHT: f64 = 24.0;
+
+/// Upper limit for the number of histogram bins per command
+const MAX_HISTOGRAM_BINS: usize = 40;
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em auto; max-width: 60em; color: #222; }
+h1, h2, h3 { font-weight: normal; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; }
+th { text-align: left; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+code { background: #f4f4f4; padding: 0.1em 0.3em; }
+svg { display: block; margin-bottom: 1em; }
+svg text { font-size: 11px; fill: #444; }
+.bar { fill: #4a7ebb; }
+.errorbar { stroke: #222; stroke-width: 1; }
+.reference { fill: #e08a2c; }
+dl { display: grid; grid-template-columns: max-content auto; gap: 0.2em 1em; }
+dt { font-weight: bold; }
+dd { margin: 0; }
+";
+
+/// Exports a self-contained HTML report, including a summary table, inline SVG charts
+/// and the raw JSON data.
+#[derive(Default)]
+pub struct HtmlExporter {}
+
+impl Exporter for HtmlExporter {
+    fn serialize(
+        &self,
+        results: &[BenchmarkResult],
+        unit: Option<Unit>,
+        sort_order: SortOrder,
+        statistic: SummaryStatistic,
+        columns: &[Column],
+        metadata: Option<&Metadata>,
+    ) -> Result<Vec<u8>> {
+        let unit = unit.unwrap_or_else(|| determine_unit_from_results(results));
+        let entries = if results.is_empty() {
+            vec![]
+        } else {
+            relative_speed::compute(results, sort_order, statistic)
+        };
+
+        let raw_json = JsonExporter::default().serialize(
+            results,
+            Some(unit),
+            sort_order,
+            statistic,
+            columns,
+            metadata,
+        )?;
+        let raw_json = String::from_utf8(raw_json)?;
+
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+        html.push_str("<meta charset=\"utf-8\">\n");
+        html.push_str("<title>hyperfine benchmark report</title>\n");
+        writeln!(html, "<style>\n{STYLE}</style>")?;
+        html.push_str("</head>\n<body>\n");
+        html.push_str("<h1>Benchmark report</h1>\n");
+
+        html.push_str(&metadata_list(results, unit, metadata));
+
+        html.push_str("<h2>Summary</h2>\n");
+        html.push_str(&summary_table(&entries, unit));
+
+        if entries.len() > 1 {
+            if statistic == SummaryStatistic::Mean {
+                html.push_str("<h2>Relative speed</h2>\n");
+            } else {
+                writeln!(html, "<h2>Relative speed (by {statistic})</h2>")?;
+            }
+            html.push_str(&comparison_chart(&entries, unit, statistic));
+        }
+
+        html.push_str("<h2>Distributions</h2>\n");
+        for entry in &entries {
+            let result = entry.result;
+            writeln!(
+                html,
+                "<h3><code>{}</code></h3>",
+                escape(&result.command_with_unused_parameters)
+            )?;
+            if let Some(times) = &result.times {
+                html.push_str(&histogram(times, unit));
+            }
+        }
+
+        html.push_str("<h2>Raw data</h2>\n");
+        html.push_str(
+            "<p><a id=\"download\" href=\"#\" download=\"hyperfine.json\">Download results as JSON</a></p>\n",
+        );
+        // A '</' sequence inside of a JSON string would terminate the <script> element early.
+        writeln!(
+            html,
+            "<script type=\"application/json\" id=\"raw-data\">\n{}</script>",
+            raw_json.replace("</", "<\\/")
+        )?;
+        html.push_str(
+            "<script>\n\
+             const data = document.getElementById('raw-data').textContent;\n\
+             const blob = new Blob([data], { type: 'application/json' });\n\
+             document.getElementById('download').href = URL.createObjectURL(blob);\n\
+             </script>\n",
+        );
+
+        html.push_str("</body>\n</html>\n");
+
+        Ok(html.into_bytes())
+    }
+}
+
+/// Escape special characters for use in HTML (or XML) text and attribute values
+pub(super) fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn format_value(value: Second, unit: Unit) -> String {
+    format!(
+        "{} {}",
+        format_duration_value(value, Some(unit)).0,
+        unit.short_name()
+    )
+}
+
+fn metadata_list(results: &[BenchmarkResult], unit: Unit, metadata: Option<&Metadata>) -> String {
+    let total_runs: usize = results
+        .iter()
+        .map(|r| r.times.as_ref().map_or(0, |t| t.len()))
+        .sum();
+
+    let mut items = match metadata {
+        Some(metadata) => metadata.entries(),
+        None => vec![("hyperfine version", crate_version!().to_string())],
+    };
+    items.extend([
+        ("Commands", results.len().to_string()),
+        ("Total runs", total_runs.to_string()),
+        ("Time unit", unit.short_name()),
+    ]);
+
+    if let Some(first) = results.first() {
+        if !first.parameters.is_empty() {
+            let names = first.parameters.keys().cloned().collect::<Vec<_>>();
+            items.push(("Parameters", names.join(", ")));
+        }
+    }
+
+    let mut list = String::from("<dl>\n");
+    for (key, value) in items {
+        let _ = writeln!(list, "<dt>{}</dt><dd>{}</dd>", key, escape(&value));
+    }
+    list.push_str("</dl>\n");
+    list
+}
+
+fn summary_table(entries: &[BenchmarkResultWithRelativeSpeed], unit: Unit) -> String {
+    let mut table = String::from("<table>\n<thead>\n<tr>");
+    for header in [
+        "Command", "Mean", "Median", "Min", "Max", "User", "System", "Runs", "Relative",
+    ] {
+        let _ = write!(table, "<th>{header}</th>");
+    }
+    table.push_str("</tr>\n</thead>\n<tbody>\n");
+
+    for entry in entries {
+        let result = entry.result;
+        let mean = match result.stddev {
+            Some(stddev) => format!(
+                "{} ± {}",
+                format_value(result.mean, unit),
+                format_duration_value(stddev, Some(unit)).0
+            ),
+            None => format_value(result.mean, unit),
+        };
+        let relative = match entry.relative_speed_stddev {
+            Some(stddev) if !entry.is_reference => {
+                format!("{:.2} ± {:.2}", entry.relative_speed, stddev)
+            }
+            _ => format!("{:.2}", entry.relative_speed),
+        };
+        let runs = result.times.as_ref().map_or(0, |t| t.len());
+
+        let _ = writeln!(
+            table,
+            "<tr><td><code>{}</code></td>{}</tr>",
+            escape(&result.command_with_unused_parameters),
+            [
+                mean,
+                format_value(result.median, unit),
+                format_value(result.min, unit),
+                format_value(result.max, unit),
+                format_value(result.user, unit),
+                format_value(result.system, unit),
+                runs.to_string(),
+                relative,
+            ]
+            .iter()
+            .map(|cell| format!("<td class=\"num\">{}</td>", escape(cell)))
+            .collect::<String>()
+        );
+    }
+
+    table.push_str("</tbody>\n</table>\n");
+    table
+}
+
+/// Horizontal bar chart of the mean times (with standard deviation error bars)
+fn comparison_chart(
+    entries: &[BenchmarkResultWithRelativeSpeed],
+    unit: Unit,
+    statistic: SummaryStatistic,
+) -> String {
+    let label_width = CHART_WIDTH / 3.0;
+    let plot_width = CHART_WIDTH - label_width - 80.0;
+    let height = BAR_HEIGHT * entries.len() as f64;
+
+    let max_time = entries
+        .iter()
+        .map(|e| e.result.statistic(statistic) + errorbar(e.result, statistic).unwrap_or(0.0))
+        .fold(0.0, f64::max);
+    let scale = if max_time > 0.0 {
+        plot_width / max_time
+    } else {
+        0.0
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{height}\" \
+         viewBox=\"0 0 {CHART_WIDTH} {height}\">\n"
+    );
+
+    for (i, entry) in entries.iter().enumerate() {
+        let result = entry.result;
+        let y = i as f64 * BAR_HEIGHT;
+        let value = result.statistic(statistic);
+        // Negative times (after subtracting the shell spawning time) are shown as empty bars
+        let bar_width = value.max(0.0) * scale;
+        let class = if entry.is_reference {
+            "bar reference"
+        } else {
+            "bar"
+        };
+
+        let _ = writeln!(
+            svg,
+            "<text x=\"0\" y=\"{:.1}\">{}</text>",
+            y + BAR_HEIGHT * 0.65,
+            escape(&truncate(&result.command_with_unused_parameters, 28))
+        );
+        let _ = writeln!(
+            svg,
+            "<rect class=\"{class}\" x=\"{label_width:.1}\" y=\"{:.1}\" width=\"{bar_width:.1}\" height=\"{:.1}\"><title>{}</title></rect>",
+            y + 4.0,
+            BAR_HEIGHT - 8.0,
+            escape(&result.command_with_unused_parameters)
+        );
+        if let Some(stddev) = errorbar(result, statistic) {
+            let x1 = label_width + (value - stddev).max(0.0) * scale;
+            let x2 = label_width + (value + stddev).max(0.0) * scale;
+            let y_mid = y + BAR_HEIGHT / 2.0;
+            let _ = writeln!(
+                svg,
+                "<line class=\"errorbar\" x1=\"{x1:.1}\" y1=\"{y_mid:.1}\" x2=\"{x2:.1}\" y2=\"{y_mid:.1}\"/>"
+            );
+        }
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\">{} ({:.2}×)</text>",
+            label_width + bar_width + 6.0,
+            y + BAR_HEIGHT * 0.65,
+            format_value(value, unit),
+            entry.relative_speed
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// The standard deviation is only shown as an error bar around the mean
+fn errorbar(result: &BenchmarkResult, statistic: SummaryStatistic) -> Option<Second> {
+    result
+        .stddev
+        .filter(|_| statistic == SummaryStatistic::Mean)
+}
+
+/// Histogram of the individual run times of a single command
+fn histogram(times: &[Second], unit: Unit) -> String {
+    if times.is_empty() {
+        return String::new();
+    }
+
+    let counts = bin_counts(times, bin_count(times.len(), MAX_HISTOGRAM_BINS));
+    let t_min = min(times);
+    let t_max = max(times);
+    let max_count = counts.iter().copied().max().unwrap_or(0).max(1);
+
+    let axis_height = 16.0;
+    let plot_height = HISTOGRAM_HEIGHT - axis_height;
+    let bin_width = CHART_WIDTH / counts.len() as f64;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{HISTOGRAM_HEIGHT}\" \
+         viewBox=\"0 0 {CHART_WIDTH} {HISTOGRAM_HEIGHT}\">\n"
+    );
+
+    for (i, &count) in counts.iter().enumerate() {
+        let height = plot_height * count as f64 / max_count as f64;
+        let _ = writeln!(
+            svg,
+            "<rect class=\"bar\" x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{height:.1}\"><title>{count}</title></rect>",
+            i as f64 * bin_width,
+            plot_height - height,
+            (bin_width - 1.0).max(1.0),
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        "<text x=\"0\" y=\"{HISTOGRAM_HEIGHT}\">{}</text>",
+        format_value(t_min, unit)
+    );
+    let _ = writeln!(
+        svg,
+        "<text x=\"{CHART_WIDTH}\" y=\"{HISTOGRAM_HEIGHT}\" text-anchor=\"end\">{}</text>",
+        format_value(t_max, unit)
+    );
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn truncate(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        s.to_string()
+    } else {
+        let mut truncated: String = s.chars().take(max_chars - 1).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+#[test]
+fn test_html_escape() {
+    assert_eq!(
+        escape("echo \"<a>\" & 'b'"),
+        "echo &quot;&lt;a&gt;&quot; &amp; &#39;b&#39;"
+    );
+}
+
+#[test]
+fn test_html_export() {
+    let results = [
+        BenchmarkResult {
+            command: String::from("sleep 0.1"),
+            command_with_unused_parameters: String::from("sleep 0.1"),
+            mean: 0.1057,
+            stddev: Some(0.0016),
+            median: 0.1057,
+            user: 0.0009,
+            system: 0.0011,
+            min: 0.1023,
+            max: 0.1080,
+            times: Some(vec![0.1023, 0.1057, 0.1080]),
+            exit_codes: vec![Some(0), Some(0), Some(0)],
+            ..Default::default()
+        },
+        BenchmarkResult {
+            command: String::from("echo '</script>' | cat"),
+            command_with_unused_parameters: String::from("echo '</script>' | cat"),
+            mean: 0.2,
+            median: 0.2,
+            user: 0.0009,
+            system: 0.0012,
+            min: 0.2,
+            max: 0.2,
+            times: Some(vec![0.2]),
+            exit_codes: vec![Some(0)],
+            ..Default::default()
+        },
+    ];
+
+    let html = String::from_utf8(
+        HtmlExporter::default()
+            .serialize(
+                &results,
+                None,
+                SortOrder::Command,
+                SummaryStatistic::Mean,
+                &Column::DEFAULT,
+                Some(&super::metadata::example()),
+            )
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<td class=\"num\">105.7 ms ± 1.6</td>"));
+    assert!(html.contains("<code>echo &#39;&lt;/script&gt;&#39; | cat</code>"));
+    assert!(html.contains("echo '<\\/script>' | cat"));
+    assert!(!html.contains("echo '</script>' | cat"));
+    assert_eq!(html.matches("<svg").count(), 3);
+    assert!(html.contains("<dt>CPU</dt><dd>AMD Ryzen 7 5800X, 16 cores</dd>"));
+    assert!(html.contains("\"cpu_governor\": \"performance\""));
+    assert!(html.trim_end().ends_with("</html>"));
+}
+
+/// The bars of the comparison chart show the selected statistic
+#[test]
+fn test_html_comparison_chart_statistic() {
+    let results = [BenchmarkResult {
+        command: String::from("sleep 1"),
+        command_with_unused_parameters: String::from("sleep 1"),
+        mean: 2.0,
+        stddev: Some(0.5),
+        median: 1.5,
+        min: 1.0,
+        max: 3.0,
+        ..Default::default()
+    }];
+    let entries = relative_speed::compute(&results, SortOrder::Command, SummaryStatistic::Median);
+
+    let chart = comparison_chart(&entries, Unit::Second, SummaryStatistic::Median);
+    assert!(chart.contains("1.500 s (1.00×)"));
+    assert!(!chart.contains("errorbar"));
+
+    let chart = comparison_chart(&entries, Unit::Second, SummaryStatistic::Mean);
+    assert!(chart.contains("2.000 s (1.00×)"));
+    assert!(chart.contains("errorbar"));
+}
@@ -1,16 +1,38 @@
+use std::borrow::Cow;
+use std::fs;
+
 use serde::*;
 use serde_json::to_vec_pretty;
 
-use super::Exporter;
+use super::markup::Column;
+use super::{Exporter, Metadata};
 use crate::benchmark::benchmark_result::BenchmarkResult;
-use crate::options::SortOrder;
+use crate::options::{SortOrder, SummaryStatistic};
 use crate::util::units::Unit;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+
+/// Version of the structure of the JSON export. It is increased whenever a change is made
+/// that could break tools which read the exported files (e.g. renamed or removed fields).
+/// The structure is described by the JSON Schema in `doc/hyperfine-results.schema.json`.
+pub const SCHEMA_VERSION: u32 = 1;
 
-#[derive(Serialize, Debug)]
+/// Contents of a JSON export
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(test, derive(schemars::JsonSchema))]
+#[cfg_attr(test, schemars(title = "hyperfine results"))]
 struct HyperfineSummary<'a> {
-    results: &'a [BenchmarkResult],
+    /// Version of the structure of this file (missing in files written by hyperfine 1.20
+    /// and earlier)
+    #[serde(default)]
+    schema_version: u32,
+
+    /// Information about the system and the hyperfine invocation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<Cow<'a, Metadata>>,
+
+    /// Results of all benchmarked commands
+    results: Cow<'a, [BenchmarkResult]>,
 }
 
 #[derive(Default)]
@@ -22,8 +44,15 @@ impl Exporter for JsonExporter {
         results: &[BenchmarkResult],
         _unit: Option<Unit>,
         _sort_order: SortOrder,
+        _statistic: SummaryStatistic,
+        _columns: &[Column],
+        metadata: Option<&Metadata>,
     ) -> Result<Vec<u8>> {
-        let mut output = to_vec_pretty(&HyperfineSummary { results });
+        let mut output = to_vec_pretty(&HyperfineSummary {
+            schema_version: SCHEMA_VERSION,
+            metadata: metadata.map(Cow::Borrowed),
+            results: Cow::Borrowed(results),
+        });
         if let Ok(ref mut content) = output {
             content.push(b'\n');
         }
@@ -31,3 +60,98 @@ impl Exporter for JsonExporter {
         Ok(output?)
     }
 }
+
+/// Read the results (and the metadata, if available) from a file that has been written
+/// with '--export-json'
+pub fn import_results(filename: &str) -> Result<(Vec<BenchmarkResult>, Option<Metadata>)> {
+    let content = fs::read_to_string(filename)
+        .with_context(|| format!("Could not read JSON results from '{filename}'"))?;
+    let summary: HyperfineSummary = serde_json::from_str(&content)
+        .with_context(|| format!("'{filename}' does not contain valid hyperfine results"))?;
+
+    if summary.schema_version > SCHEMA_VERSION {
+        bail!(
+            "'{filename}' has been written by a newer version of hyperfine (schema version {}, \
+             supported: {SCHEMA_VERSION}).",
+            summary.schema_version
+        );
+    }
+    if summary.results.is_empty() {
+        bail!("'{filename}' does not contain any benchmark results.");
+    }
+
+    let mut results = summary.results.into_owned();
+    for result in &mut results {
+        result.command_with_unused_parameters = result.command.clone();
+    }
+
+    Ok((results, summary.metadata.map(Cow::into_owned)))
+}
+
+#[cfg(test)]
+fn test_results() -> Vec<BenchmarkResult> {
+    use crate::benchmark::benchmark_result::Percentiles;
+
+    vec![BenchmarkResult {
+        command: String::from("sleep 0.1"),
+        command_with_unused_parameters: String::from("sleep 0.1"),
+        mean: 0.1,
+        stddev: Some(0.01),
+        median: 0.1,
+        user: 0.002,
+        system: 0.001,
+        min: 0.09,
+        max: 0.11,
+        percentiles: Percentiles(vec![(5.0, 0.09), (50.0, 0.1), (99.9, 0.11)]),
+        times: Some(vec![0.09, 0.1, 0.11]),
+        outliers: vec![2],
+        exit_codes: vec![Some(0), Some(0), None],
+        parameters: vec![(String::from("delay"), String::from("0.1"))]
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    }]
+}
+
+#[test]
+fn test_json_round_trip() {
+    use std::io::Write;
+
+    let results = test_results();
+    let output = JsonExporter::default()
+        .serialize(
+            &results,
+            None,
+            SortOrder::Command,
+            SummaryStatistic::Mean,
+            &[],
+            None,
+        )
+        .unwrap();
+
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(&output).unwrap();
+    let (imported, metadata) = import_results(file.path().to_str().unwrap()).unwrap();
+
+    assert_eq!(imported, results);
+    assert_eq!(metadata, None);
+}
+
+#[test]
+fn test_json_schema_is_up_to_date() {
+    use std::path::Path;
+
+    let schema = schemars::schema_for!(HyperfineSummary);
+    let schema = serde_json::to_string_pretty(&schema).unwrap() + "\n";
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("doc/hyperfine-results.schema.json");
+    if std::env::var_os("UPDATE_JSON_SCHEMA").is_some() {
+        fs::write(&path, &schema).unwrap();
+    }
+    assert!(
+        fs::read_to_string(&path).ok().as_ref() == Some(&schema),
+        "The JSON Schema in '{}' is out of date. Run 'UPDATE_JSON_SCHEMA=1 cargo test' to \
+         update it (and increase SCHEMA_VERSION for incompatible changes).",
+        path.display()
+    );
+}
@@ -0,0 +1,270 @@
+use std::fmt::Write as _;
+
+use super::html::escape;
+use super::markup::Column;
+use super::{Exporter, Metadata};
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::benchmark::relative_speed;
+use crate::options::{SortOrder, SummaryStatistic};
+use crate::util::units::{Scalar, Unit};
+
+use anyhow::Result;
+
+/// Exports the results as JUnit XML report, with one test case per benchmarked command.
+/// A test case fails if the command returned a non-zero exit code or (optionally) if it
+/// is slower than the reference by more than a given factor.
+#[derive(Default)]
+pub struct JunitExporter {
+    /// Maximum ratio between the run time of a command and the run time of the reference
+    regression_threshold: Option<Scalar>,
+
+    /// Whether the first result belongs to the '--reference' command. Otherwise, the
+    /// fastest command is used as reference.
+    first_is_reference: bool,
+}
+
+impl JunitExporter {
+    pub fn new(regression_threshold: Option<Scalar>, first_is_reference: bool) -> Self {
+        JunitExporter {
+            regression_threshold,
+            first_is_reference,
+        }
+    }
+
+    /// Reasons for marking the benchmark of a command as failed
+    fn failures(
+        &self,
+        result: &BenchmarkResult,
+        reference: &BenchmarkResult,
+        statistic: SummaryStatistic,
+    ) -> Vec<String> {
+        let mut failures = vec![];
+
+        let failed_runs = result
+            .exit_codes
+            .iter()
+            .filter(|code| **code != Some(0))
+            .count();
+        if failed_runs > 0 {
+            failures.push(format!(
+                "Command terminated with a non-zero exit code in {failed_runs} of {} runs",
+                result.exit_codes.len()
+            ));
+        }
+
+        let reference_value = reference.statistic(statistic);
+        if let Some(threshold) = self.regression_threshold {
+            if result != reference && reference_value > 0.0 {
+                let ratio = result.statistic(statistic) / reference_value;
+                if ratio > threshold {
+                    failures.push(format!(
+                        "Command is {ratio:.2} times slower than '{}' (threshold: {threshold:.2})",
+                        reference.command
+                    ));
+                }
+            }
+        }
+
+        failures
+    }
+}
+
+impl Exporter for JunitExporter {
+    fn serialize(
+        &self,
+        results: &[BenchmarkResult],
+        _unit: Option<Unit>,
+        sort_order: SortOrder,
+        statistic: SummaryStatistic,
+        _columns: &[Column],
+        metadata: Option<&Metadata>,
+    ) -> Result<Vec<u8>> {
+        let mut testcases = String::new();
+        let mut failure_count = 0;
+        if !results.is_empty() {
+            let reference = if self.first_is_reference {
+                &results[0]
+            } else {
+                relative_speed::fastest_of(results, statistic)
+            };
+
+            let mut results: Vec<_> = results.iter().collect();
+            if sort_order == SortOrder::MeanTime {
+                results.sort_by(|l, r| relative_speed::compare_statistic(l, r, statistic));
+            }
+
+            for result in results {
+                let failures = self.failures(result, reference, statistic);
+                if !failures.is_empty() {
+                    failure_count += 1;
+                }
+                testcases.push_str(&testcase(result, reference, statistic, &failures)?);
+            }
+        }
+
+        let total_time: Scalar = results.iter().map(|result| result.mean).sum();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        writeln!(
+            xml,
+            "<testsuites name=\"hyperfine\" tests=\"{}\" failures=\"{failure_count}\" time=\"{total_time:.6}\">",
+            results.len()
+        )?;
+        write!(
+            xml,
+            "  <testsuite name=\"hyperfine\" tests=\"{}\" failures=\"{failure_count}\" errors=\"0\" \
+             skipped=\"0\" time=\"{total_time:.6}\"",
+            results.len()
+        )?;
+        if let Some(metadata) = metadata {
+            write!(xml, " timestamp=\"{}\"", escape(&metadata.start_time))?;
+            if let Some(hostname) = &metadata.hostname {
+                write!(xml, " hostname=\"{}\"", escape(hostname))?;
+            }
+        }
+        xml.push_str(">\n");
+
+        if let Some(metadata) = metadata {
+            xml.push_str("    <properties>\n");
+            for (name, value) in metadata.entries() {
+                xml.push_str(&property(6, name, &value));
+            }
+            xml.push_str("    </properties>\n");
+        }
+
+        xml.push_str(&testcases);
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+
+        Ok(xml.into_bytes())
+    }
+}
+
+fn property(indent: usize, name: &str, value: &str) -> String {
+    format!(
+        "{:indent$}<property name=\"{}\" value=\"{}\"/>\n",
+        "",
+        escape(name),
+        escape(value)
+    )
+}
+
+fn testcase(
+    result: &BenchmarkResult,
+    reference: &BenchmarkResult,
+    statistic: SummaryStatistic,
+    failures: &[String],
+) -> Result<String> {
+    let mut xml = String::new();
+    writeln!(
+        xml,
+        "    <testcase name=\"{}\" classname=\"hyperfine\" time=\"{:.6}\">",
+        escape(&result.command_with_unused_parameters),
+        result.mean
+    )?;
+
+    // All times are given in seconds
+    xml.push_str("      <properties>\n");
+    let mut properties = vec![("mean", result.mean.to_string())];
+    if let Some(stddev) = result.stddev {
+        properties.push(("stddev", stddev.to_string()));
+    }
+    properties.push(("median", result.median.to_string()));
+    properties.push(("min", result.min.to_string()));
+    properties.push(("max", result.max.to_string()));
+    properties.push(("user", result.user.to_string()));
+    properties.push(("system", result.system.to_string()));
+    properties.push(("runs", result.exit_codes.len().to_string()));
+    if reference.statistic(statistic) > 0.0 {
+        let ratio = result.statistic(statistic) / reference.statistic(statistic);
+        properties.push(("relative", format!("{ratio:.3}")));
+    }
+    for (name, value) in properties {
+        xml.push_str(&property(8, name, &value));
+    }
+    for (name, value) in &result.parameters {
+        xml.push_str(&property(8, &format!("parameter.{name}"), value));
+    }
+    xml.push_str("      </properties>\n");
+
+    if !failures.is_empty() {
+        writeln!(
+            xml,
+            "      <failure message=\"{}\" type=\"BenchmarkFailure\">{}</failure>",
+            escape(&failures.join("; ")),
+            escape(&failures.join("\n"))
+        )?;
+    }
+    xml.push_str("    </testcase>\n");
+
+    Ok(xml)
+}
+
+#[cfg(test)]
+fn create_result(command: &str, mean: Scalar, exit_codes: Vec<Option<i32>>) -> BenchmarkResult {
+    BenchmarkResult {
+        command: command.into(),
+        command_with_unused_parameters: command.into(),
+        mean,
+        stddev: Some(0.001),
+        median: mean,
+        min: mean,
+        max: mean,
+        exit_codes,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+fn export(exporter: JunitExporter, results: &[BenchmarkResult]) -> String {
+    let output = exporter
+        .serialize(
+            results,
+            None,
+            SortOrder::Command,
+            SummaryStatistic::Mean,
+            &[],
+            None,
+        )
+        .unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn test_junit_export() {
+    let mut results = vec![
+        create_result("sleep 0.1", 0.1, vec![Some(0), Some(0)]),
+        create_result("sleep 0.2 && exit 1", 0.2, vec![Some(1), None]),
+        create_result("sleep 0.105", 0.105, vec![Some(0), Some(0)]),
+    ];
+    results[2]
+        .parameters
+        .insert(String::from("delay"), String::from("0.105"));
+
+    let xml = export(JunitExporter::default(), &results);
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites"));
+    assert!(xml.contains("tests=\"3\" failures=\"1\""));
+    assert!(xml.contains(
+        "<testcase name=\"sleep 0.2 &amp;&amp; exit 1\" classname=\"hyperfine\" time=\"0.200000\">"
+    ));
+    assert!(xml.contains(
+        "<failure message=\"Command terminated with a non-zero exit code in 2 of 2 runs\""
+    ));
+    assert!(xml.contains("<property name=\"stddev\" value=\"0.001\"/>"));
+    assert!(xml.contains("<property name=\"relative\" value=\"2.000\"/>"));
+    assert!(xml.contains("<property name=\"parameter.delay\" value=\"0.105\"/>"));
+    assert!(xml.ends_with("  </testsuite>\n</testsuites>\n"));
+
+    let xml = export(JunitExporter::new(Some(1.1), false), &results);
+    assert!(xml.contains("failures=\"1\""));
+    assert!(xml.contains("Command is 2.00 times slower than &#39;sleep 0.1&#39;"));
+
+    let xml = export(JunitExporter::new(Some(1.01), false), &results);
+    assert!(xml.contains("failures=\"2\""));
+    assert!(xml.contains("Command is 1.05 times slower than &#39;sleep 0.1&#39;"));
+
+    results.swap(0, 2);
+    let xml = export(JunitExporter::new(Some(1.01), true), &results);
+    assert!(xml.contains("failures=\"1\""));
+    assert!(xml.contains("Command is 1.90 times slower than &#39;sleep 0.105&#39;"));
+    assert!(!xml.contains("than &#39;sleep 0.1&#39;"));
+}
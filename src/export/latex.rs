@@ -0,0 +1,132 @@
+use super::markup::Alignment;
+use crate::export::markup::MarkupExporter;
+
+/// Exports LaTeX tables in the style of the `booktabs` package
+#[derive(Default)]
+pub struct LatexExporter {}
+
+impl MarkupExporter for LatexExporter {
+    fn table_header(&self, cell_aligmnents: &[Alignment]) -> String {
+        format!(
+            "\\begin{{tabular}}{{{}}}\n\\toprule\n",
+            cell_aligmnents
+                .iter()
+                .map(|a| match a {
+                    Alignment::Left => "l",
+                    Alignment::Right => "r",
+                })
+                .collect::<String>()
+        )
+    }
+
+    fn table_footer(&self, _cell_aligmnents: &[Alignment]) -> String {
+        "\\bottomrule\n\\end{tabular}\n".to_string()
+    }
+
+    fn table_row(&self, cells: &[&str]) -> String {
+        // Units and uncertainties are typeset in math mode, such that no special
+        // input encoding is required
+        format!(
+            "{} \\\\\n",
+            cells
+                .join(" & ")
+                .replace('±', "$\\pm$")
+                .replace('µ', "$\\mu$")
+        )
+    }
+
+    fn table_divider(&self, _cell_aligmnents: &[Alignment]) -> String {
+        "\\midrule\n".to_string()
+    }
+
+    fn command(&self, cmd: &str) -> String {
+        format!("\\texttt{{{cmd}}}")
+    }
+
+    fn escape(&self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '\\' => escaped.push_str("\\textbackslash{}"),
+                '~' => escaped.push_str("\\textasciitilde{}"),
+                '^' => escaped.push_str("\\textasciicircum{}"),
+                '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                _ => escaped.push(c),
+            }
+        }
+        escaped
+    }
+}
+
+/// Check LaTeX table header formatting
+#[test]
+fn test_latex_exporter_table_header() {
+    let exporter = LatexExporter::default();
+
+    let actual = exporter.table_header(&[Alignment::Left, Alignment::Right, Alignment::Right]);
+    let expect = "\\begin{tabular}{lrr}\n\\toprule\n";
+
+    assert_eq!(expect, actual);
+}
+
+/// Check LaTeX data row formatting
+#[test]
+fn test_latex_exporter_table_data() {
+    let exporter = LatexExporter::default();
+
+    let actual = exporter.table_row(&["\\texttt{a}", "1.0 ± 0.1", "Mean [µs]"]);
+    let expect = "\\texttt{a} & 1.0 $\\pm$ 0.1 & Mean [$\\mu$s] \\\\\n";
+
+    assert_eq!(expect, actual);
+}
+
+/// Check escaping of special characters
+#[test]
+fn test_latex_exporter_escape() {
+    let exporter = LatexExporter::default();
+
+    assert_eq!(
+        exporter.escape("grep -E '^a{2}$' ~/x_1.txt | wc -l && echo 100% #\\"),
+        "grep -E '\\textasciicircum{}a\\{2\\}\\$' \\textasciitilde{}/x\\_1.txt | wc -l \
+         \\&\\& echo 100\\% \\#\\textbackslash{}"
+    );
+}
+
+/// Check that the names of parameter columns are escaped
+#[test]
+fn test_latex_exporter_parameter_column() {
+    use super::markup::Column;
+    use crate::benchmark::benchmark_result::BenchmarkResult;
+    use crate::benchmark::relative_speed;
+    use crate::options::{SortOrder, SummaryStatistic};
+    use crate::util::units::Unit;
+
+    let results = [BenchmarkResult {
+        command: String::from("make -j 4"),
+        command_with_unused_parameters: String::from("make -j 4"),
+        mean: 1.5,
+        parameters: vec![(String::from("num_threads"), String::from("4"))]
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    }];
+    let entries = relative_speed::compute(&results, SortOrder::Command, SummaryStatistic::Mean);
+
+    let actual = LatexExporter::default().table_results(
+        &entries,
+        Unit::Second,
+        &Column::parse_list("command,parameter_num_threads").unwrap(),
+    );
+    let expect = "\\begin{tabular}{ll}\n\
+                  \\toprule\n\
+                  Command & num\\_threads \\\\\n\
+                  \\midrule\n\
+                  \\texttt{make -j 4} & 4 \\\\\n\
+                  \\bottomrule\n\
+                  \\end{tabular}\n";
+
+    assert_eq!(expect, actual);
+}
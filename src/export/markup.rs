@@ -1,10 +1,14 @@
-use crate::benchmark::relative_speed::BenchmarkResultWithRelativeSpeed;
-use crate::benchmark::{benchmark_result::BenchmarkResult, relative_speed};
-use crate::options::SortOrder;
+use std::iter;
+use std::str::FromStr;
+
+use crate::benchmark::benchmark_result::{BenchmarkResult, Percentiles};
+use crate::benchmark::relative_speed::{self, BenchmarkResultWithRelativeSpeed};
+use crate::error::OptionsError;
+use crate::options::{SortOrder, SummaryStatistic};
 use crate::output::format::format_duration_value;
-use crate::util::units::Unit;
+use crate::util::units::{Scalar, Unit};
 
-use super::Exporter;
+use super::{Exporter, Metadata};
 use anyhow::Result;
 
 pub enum Alignment {
@@ -12,74 +16,219 @@ pub enum Alignment {
     Right,
 }
 
-pub trait MarkupExporter {
-    fn table_results(&self, entries: &[BenchmarkResultWithRelativeSpeed], unit: Unit) -> String {
-        // prepare table header strings
+/// A column of the exported markup tables
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    Command,
+    Mean,
+    Median,
+    Min,
+    Max,
+    Percentile(Scalar),
+    User,
+    System,
+    /// The maximum memory usage over all runs
+    Memory,
+    Relative,
+    /// The value of the parameter with the given name
+    Parameter(String),
+}
+
+impl Column {
+    /// The columns that are exported if nothing else has been specified
+    pub const DEFAULT: [Column; 5] = [
+        Column::Command,
+        Column::Mean,
+        Column::Min,
+        Column::Max,
+        Column::Relative,
+    ];
+
+    /// Parse a comma-separated list of column names like "command,mean,p95,relative"
+    /// (parameter columns are selected with their name, like "parameter_threads")
+    pub fn parse_list(list: &str) -> Result<Vec<Column>, OptionsError<'static>> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse())
+            .collect()
+    }
+
+    fn alignment(&self) -> Alignment {
+        match self {
+            Column::Command | Column::Parameter(_) => Alignment::Left,
+            _ => Alignment::Right,
+        }
+    }
+
+    fn header(&self, unit: Unit) -> String {
         let notation = format!("[{}]", unit.short_name());
+        match self {
+            Column::Command => "Command".into(),
+            Column::Mean => format!("Mean {notation}"),
+            Column::Median => format!("Median {notation}"),
+            Column::Min => format!("Min {notation}"),
+            Column::Max => format!("Max {notation}"),
+            Column::Percentile(p) => format!("{} {notation}", Percentiles::name(*p)),
+            Column::User => format!("User {notation}"),
+            Column::System => format!("System {notation}"),
+            Column::Memory => "Memory [MiB]".into(),
+            Column::Relative => "Relative".into(),
+            Column::Parameter(name) => name.clone(),
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = OptionsError<'static>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "command" => Ok(Column::Command),
+            "mean" => Ok(Column::Mean),
+            "median" => Ok(Column::Median),
+            "min" => Ok(Column::Min),
+            "max" => Ok(Column::Max),
+            "user" => Ok(Column::User),
+            "system" => Ok(Column::System),
+            "memory" => Ok(Column::Memory),
+            "relative" => Ok(Column::Relative),
+            _ if s.starts_with("parameter_") && s.len() > "parameter_".len() => {
+                Ok(Column::Parameter(s["parameter_".len()..].to_string()))
+            }
+            _ => Percentiles::parse_name(s)
+                .map(Column::Percentile)
+                .ok_or_else(|| OptionsError::UnknownExportColumn(s.to_string())),
+        }
+    }
+}
 
+pub trait MarkupExporter {
+    fn table_results(
+        &self,
+        entries: &[BenchmarkResultWithRelativeSpeed],
+        unit: Unit,
+        columns: &[Column],
+    ) -> String {
         // prepare table cells alignment
-        let cells_alignment = [
-            Alignment::Left,
-            Alignment::Right,
-            Alignment::Right,
-            Alignment::Right,
-            Alignment::Right,
-        ];
+        let cells_alignment: Vec<Alignment> = columns.iter().map(Column::alignment).collect();
+
+        // prepare table header data
+        let headers: Vec<String> = columns
+            .iter()
+            .map(|c| self.escape(&c.header(unit)))
+            .collect();
 
+        // prepare data row strings
+        let rows: Vec<Vec<String>> = entries
+            .iter()
+            .map(|entry| {
+                columns
+                    .iter()
+                    .map(|column| self.table_cell(entry, column, unit))
+                    .collect()
+            })
+            .collect();
+
+        self.table(&cells_alignment, &headers, &rows)
+    }
+
+    /// A two-column table with information about the system and the hyperfine invocation
+    fn metadata_table(&self, metadata: &Metadata) -> String {
+        let rows: Vec<Vec<String>> = metadata
+            .entries()
+            .into_iter()
+            .map(|(key, value)| vec![key.to_string(), self.escape(&value)])
+            .collect();
+
+        self.table(
+            &[Alignment::Left, Alignment::Left],
+            &["Property".to_string(), "Value".to_string()],
+            &rows,
+        )
+    }
+
+    /// Assemble a table from the given header and data rows
+    fn table(
+        &self,
+        cells_alignment: &[Alignment],
+        headers: &[String],
+        rows: &[Vec<String>],
+    ) -> String {
         // emit table header format
-        let mut table = self.table_header(&cells_alignment);
+        let mut table = self.table_header(cells_alignment);
 
         // emit table header data
-        table.push_str(&self.table_row(&[
-            "Command",
-            &format!("Mean {notation}"),
-            &format!("Min {notation}"),
-            &format!("Max {notation}"),
-            "Relative",
-        ]));
+        table.push_str(&self.table_row(&as_strs(headers)));
 
         // emit horizontal line
-        table.push_str(&self.table_divider(&cells_alignment));
-
-        for entry in entries {
-            let measurement = &entry.result;
-            // prepare data row strings
-            let cmd_str = measurement
-                .command_with_unused_parameters
-                .replace('|', "\\|");
-            let mean_str = format_duration_value(measurement.mean, Some(unit)).0;
-            let stddev_str = if let Some(stddev) = measurement.stddev {
-                format!(" ± {}", format_duration_value(stddev, Some(unit)).0)
-            } else {
-                "".into()
-            };
-            let min_str = format_duration_value(measurement.min, Some(unit)).0;
-            let max_str = format_duration_value(measurement.max, Some(unit)).0;
-            let rel_str = format!("{:.2}", entry.relative_speed);
-            let rel_stddev_str = if entry.is_reference {
-                "".into()
-            } else if let Some(stddev) = entry.relative_speed_stddev {
-                format!(" ± {stddev:.2}")
-            } else {
-                "".into()
-            };
-
-            // prepare table row entries
-            table.push_str(&self.table_row(&[
-                &self.command(&cmd_str),
-                &format!("{mean_str}{stddev_str}"),
-                &min_str,
-                &max_str,
-                &format!("{rel_str}{rel_stddev_str}"),
-            ]))
+        table.push_str(&self.table_divider(cells_alignment));
+
+        for row in rows {
+            table.push_str(&self.table_row(&as_strs(row)));
         }
 
         // emit table footer format
-        table.push_str(&self.table_footer(&cells_alignment));
+        table.push_str(&self.table_footer(cells_alignment));
 
         table
     }
 
+    fn table_cell(
+        &self,
+        entry: &BenchmarkResultWithRelativeSpeed,
+        column: &Column,
+        unit: Unit,
+    ) -> String {
+        let measurement = &entry.result;
+        match column {
+            Column::Command => {
+                let cmd_str = self.escape(&measurement.command_with_unused_parameters);
+                self.command(&cmd_str)
+            }
+            Column::Mean => {
+                let mean_str = format_duration_value(measurement.mean, Some(unit)).0;
+                let stddev_str = if let Some(stddev) = measurement.stddev {
+                    format!(" ± {}", format_duration_value(stddev, Some(unit)).0)
+                } else {
+                    "".into()
+                };
+                format!("{mean_str}{stddev_str}")
+            }
+            Column::Median => format_duration_value(measurement.median, Some(unit)).0,
+            Column::Min => format_duration_value(measurement.min, Some(unit)).0,
+            Column::Max => format_duration_value(measurement.max, Some(unit)).0,
+            Column::Percentile(p) => measurement
+                .percentile(*p)
+                .map(|value| format_duration_value(value, Some(unit)).0)
+                .unwrap_or_default(),
+            Column::User => format_duration_value(measurement.user, Some(unit)).0,
+            Column::System => format_duration_value(measurement.system, Some(unit)).0,
+            Column::Memory => measurement
+                .memory_usage_byte
+                .as_ref()
+                .and_then(|memory| memory.iter().max())
+                .map(|&max| format!("{:.1}", max as f64 / (1024.0 * 1024.0)))
+                .unwrap_or_default(),
+            Column::Relative => {
+                let rel_str = format!("{:.2}", entry.relative_speed);
+                let rel_stddev_str = if entry.is_reference {
+                    "".into()
+                } else if let Some(stddev) = entry.relative_speed_stddev {
+                    format!(" ± {stddev:.2}")
+                } else {
+                    "".into()
+                };
+                format!("{rel_str}{rel_stddev_str}")
+            }
+            Column::Parameter(name) => measurement
+                .parameters
+                .get(name)
+                .map(|value| self.escape(value))
+                .unwrap_or_default(),
+        }
+    }
+
     fn table_row(&self, cells: &[&str]) -> String;
 
     fn table_divider(&self, cell_aligmnents: &[Alignment]) -> String;
@@ -93,9 +242,49 @@ pub trait MarkupExporter {
     }
 
     fn command(&self, size: &str) -> String;
+
+    /// Escape characters with a special meaning in table cells (in the command and the
+    /// metadata values)
+    fn escape(&self, text: &str) -> String {
+        text.replace('|', "\\|")
+    }
+}
+
+fn as_strs(cells: &[String]) -> Vec<&str> {
+    cells.iter().map(|c| c.as_str()).collect()
 }
 
-fn determine_unit_from_results(results: &[BenchmarkResult]) -> Unit {
+/// The width (in characters) of every column, such that all cells fit into it
+pub(super) fn column_widths(headers: &[String], rows: &[Vec<String>]) -> Vec<usize> {
+    (0..headers.len())
+        .map(|i| {
+            iter::once(headers)
+                .chain(rows.iter().map(|row| row.as_slice()))
+                .map(|cells| cells[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Pad the cells of a row to the given column widths
+pub(super) fn pad_cells(
+    cells: &[String],
+    widths: &[usize],
+    cells_alignment: &[Alignment],
+) -> Vec<String> {
+    cells
+        .iter()
+        .zip(widths)
+        .zip(cells_alignment)
+        .map(|((cell, &width), alignment)| match alignment {
+            Alignment::Left => format!("{cell:<width$}"),
+            Alignment::Right => format!("{cell:>width$}"),
+        })
+        .collect()
+}
+
+pub(super) fn determine_unit_from_results(results: &[BenchmarkResult]) -> Unit {
     if let Some(first_result) = results.first() {
         // Use the first BenchmarkResult entry to determine the unit for all entries.
         format_duration_value(first_result.mean, None).1
@@ -111,11 +300,19 @@ impl<T: MarkupExporter> Exporter for T {
         results: &[BenchmarkResult],
         unit: Option<Unit>,
         sort_order: SortOrder,
+        statistic: SummaryStatistic,
+        columns: &[Column],
+        metadata: Option<&Metadata>,
     ) -> Result<Vec<u8>> {
         let unit = unit.unwrap_or_else(|| determine_unit_from_results(results));
-        let entries = relative_speed::compute(results, sort_order);
+        let entries = relative_speed::compute(results, sort_order, statistic);
 
-        let table = self.table_results(&entries, unit);
-        Ok(table.as_bytes().to_vec())
+        let mut output = String::new();
+        if let Some(metadata) = metadata {
+            output.push_str(&self.metadata_table(metadata));
+            output.push('\n');
+        }
+        output.push_str(&self.table_results(&entries, unit, columns));
+        Ok(output.into_bytes())
     }
 }
@@ -0,0 +1,240 @@
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::crate_version;
+use serde::*;
+
+use crate::options::ExecutorKind;
+use crate::util::system_info;
+
+/// Information about the system and the hyperfine invocation that produced a set of results
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(test, derive(schemars::JsonSchema))]
+pub struct Metadata {
+    /// Version of hyperfine
+    pub hyperfine_version: String,
+
+    /// The full command line that hyperfine has been called with
+    pub invocation: Vec<String>,
+
+    /// Name of the host
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+
+    /// Operating system family, e.g. "linux"
+    pub os: String,
+
+    /// Human readable name of the operating system (distribution)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os_name: Option<String>,
+
+    /// Release of the operating system kernel
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kernel: Option<String>,
+
+    /// CPU architecture, e.g. "x86_64"
+    pub arch: String,
+
+    /// Model name of the CPU
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_model: Option<String>,
+
+    /// Number of logical CPU cores
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_cores: Option<usize>,
+
+    /// Total physical memory in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_total_bytes: Option<u64>,
+
+    /// CPU frequency scaling governor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_governor: Option<String>,
+
+    /// System load averages (1, 5 and 15 minutes) when hyperfine was started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub load_average: Option<[f64; 3]>,
+
+    /// Shell that is used to execute the benchmarked commands (if any)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+
+    /// Time when hyperfine was started (RFC 3339, UTC)
+    pub start_time: String,
+
+    /// Time when the results have been exported (RFC 3339, UTC)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<String>,
+}
+
+impl Metadata {
+    /// Collect the metadata for the current system and process. This should be called
+    /// before the benchmarks are started.
+    pub fn collect(executor_kind: &ExecutorKind) -> Self {
+        Metadata {
+            hyperfine_version: crate_version!().to_string(),
+            invocation: env::args_os()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+            hostname: system_info::hostname(),
+            os: env::consts::OS.to_string(),
+            os_name: system_info::os_name(),
+            kernel: system_info::kernel_release(),
+            arch: env::consts::ARCH.to_string(),
+            cpu_model: system_info::cpu_model(),
+            cpu_cores: system_info::cpu_cores(),
+            memory_total_bytes: system_info::total_memory(),
+            cpu_governor: system_info::cpu_governor(0),
+            load_average: system_info::load_average(),
+            shell: match executor_kind {
+                ExecutorKind::Shell(shell) => Some(shell.to_string()),
+                _ => None,
+            },
+            start_time: format_timestamp(SystemTime::now()),
+            end_time: None,
+        }
+    }
+
+    /// A copy of this metadata with the end time set to the current time (unless it is
+    /// already known, for results that have been imported with '--from-json')
+    pub fn finished_now(&self) -> Self {
+        Metadata {
+            end_time: self
+                .end_time
+                .clone()
+                .or_else(|| Some(format_timestamp(SystemTime::now()))),
+            ..self.clone()
+        }
+    }
+
+    /// Human readable key/value pairs for the header of report-style exports
+    pub fn entries(&self) -> Vec<(&'static str, String)> {
+        let system = match (&self.os_name, &self.kernel) {
+            (Some(name), Some(kernel)) => format!("{name}, kernel {kernel} ({})", self.arch),
+            (None, Some(kernel)) => format!("{} {kernel} ({})", self.os, self.arch),
+            (Some(name), None) => format!("{name} ({})", self.arch),
+            (None, None) => format!("{} ({})", self.os, self.arch),
+        };
+        let cpu = match (&self.cpu_model, self.cpu_cores) {
+            (Some(model), Some(cores)) => Some(format!("{model}, {cores} cores")),
+            (Some(model), None) => Some(model.clone()),
+            (None, Some(cores)) => Some(format!("{cores} cores")),
+            (None, None) => None,
+        };
+
+        let mut entries = vec![("hyperfine version", self.hyperfine_version.clone())];
+        if let Some(hostname) = &self.hostname {
+            entries.push(("Host", hostname.clone()));
+        }
+        entries.push(("System", system));
+        if let Some(cpu) = cpu {
+            entries.push(("CPU", cpu));
+        }
+        if let Some(memory) = self.memory_total_bytes {
+            entries.push((
+                "Memory",
+                format!("{:.1} GiB", memory as f64 / (1024.0 * 1024.0 * 1024.0)),
+            ));
+        }
+        if let Some(governor) = &self.cpu_governor {
+            entries.push(("CPU governor", governor.clone()));
+        }
+        if let Some([l1, l5, l15]) = self.load_average {
+            entries.push(("Load average", format!("{l1:.2}, {l5:.2}, {l15:.2}")));
+        }
+        if let Some(shell) = &self.shell {
+            entries.push(("Shell", shell.clone()));
+        }
+        entries.push(("Invocation", shell_words::join(&self.invocation)));
+        entries.push(("Started", self.start_time.clone()));
+        if let Some(end_time) = &self.end_time {
+            entries.push(("Finished", end_time.clone()));
+        }
+        entries
+    }
+}
+
+/// Format the given time as an RFC 3339 timestamp in UTC, e.g. "2024-03-01T12:34:56Z"
+fn format_timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let (days, seconds_of_day) = (seconds / 86400, seconds % 86400);
+    let (year, month, day) = civil_from_days(days as i64);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds_of_day / 3600,
+        (seconds_of_day % 3600) / 60,
+        seconds_of_day % 60
+    )
+}
+
+/// Convert the number of days since 1970-01-01 into a (year, month, day) date in the
+/// proleptic Gregorian calendar (see http://howardhinnant.github.io/date_algorithms.html)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+pub fn example() -> Metadata {
+    Metadata {
+        hyperfine_version: "1.20.0".into(),
+        invocation: vec!["hyperfine".into(), "sleep 0.1".into()],
+        hostname: Some("benchmark-host".into()),
+        os: "linux".into(),
+        os_name: Some("Debian GNU/Linux 12 (bookworm)".into()),
+        kernel: Some("6.1.0-18-amd64".into()),
+        arch: "x86_64".into(),
+        cpu_model: Some("AMD Ryzen 7 5800X".into()),
+        cpu_cores: Some(16),
+        memory_total_bytes: Some(34359738368),
+        cpu_governor: Some("performance".into()),
+        load_average: Some([0.5, 0.25, 0.75]),
+        shell: Some("sh".into()),
+        start_time: "2024-03-01T12:00:00Z".into(),
+        end_time: Some("2024-03-01T12:00:05Z".into()),
+    }
+}
+
+#[test]
+fn test_format_timestamp() {
+    use std::time::Duration;
+
+    assert_eq!(format_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+    assert_eq!(
+        format_timestamp(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+        "2000-02-29T00:00:00Z"
+    );
+    assert_eq!(
+        format_timestamp(UNIX_EPOCH + Duration::from_secs(1_709_296_496)),
+        "2024-03-01T12:34:56Z"
+    );
+}
+
+#[test]
+fn test_metadata_entries() {
+    let entries = example().entries();
+    let get = |key| entries.iter().find(|(k, _)| *k == key).unwrap().1.as_str();
+
+    assert_eq!(
+        get("System"),
+        "Debian GNU/Linux 12 (bookworm), kernel 6.1.0-18-amd64 (x86_64)"
+    );
+    assert_eq!(get("CPU"), "AMD Ryzen 7 5800X, 16 cores");
+    assert_eq!(get("Memory"), "32.0 GiB");
+    assert_eq!(get("Load average"), "0.50, 0.25, 0.75");
+    assert_eq!(get("Invocation"), "hyperfine 'sleep 0.1'");
+}
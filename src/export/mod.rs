@@ -2,23 +2,48 @@ use std::fs::{File, OpenOptions};
 use std::io::Write;
 
 mod asciidoc;
+mod bencher;
 mod csv;
+mod github_benchmark;
+mod html;
 mod json;
+mod junit;
+mod latex;
 mod markdown;
 mod markup;
+mod metadata;
+mod ndjson;
+mod openmetrics;
 mod orgmode;
+mod rst;
+mod terminal;
 #[cfg(test)]
 mod tests;
 
 use self::asciidoc::AsciidocExporter;
+use self::bencher::BencherExporter;
 use self::csv::CsvExporter;
+use self::github_benchmark::GithubBenchmarkExporter;
+use self::html::HtmlExporter;
 use self::json::JsonExporter;
+use self::junit::JunitExporter;
+use self::latex::LatexExporter;
 use self::markdown::MarkdownExporter;
+use self::markup::{determine_unit_from_results, Column, MarkupExporter};
+use self::openmetrics::OpenMetricsExporter;
 use self::orgmode::OrgmodeExporter;
+use self::rst::RstExporter;
+use self::terminal::TerminalExporter;
+
+pub use self::json::import_results;
+pub use self::metadata::Metadata;
+pub use self::ndjson::{Event, EventStream};
 
 use crate::benchmark::benchmark_result::BenchmarkResult;
-use crate::options::SortOrder;
-use crate::util::units::Unit;
+use crate::benchmark::relative_speed;
+use crate::error::OptionsError;
+use crate::options::{SortOrder, SummaryStatistic};
+use crate::util::units::{Scalar, Unit};
 
 use anyhow::{Context, Result};
 use clap::ArgMatches;
@@ -29,17 +54,70 @@ pub enum ExportType {
     /// Asciidoc Table
     Asciidoc,
 
+    /// Bencher Metric Format (JSON)
+    Bencher,
+
     /// CSV (comma separated values) format
     Csv,
 
+    /// "customSmallerIsBetter" format of github-action-benchmark (JSON)
+    GithubBenchmark,
+
+    /// Self-contained HTML report
+    Html,
+
     /// JSON format
     Json,
 
+    /// JUnit XML report
+    Junit,
+
+    /// LaTeX table (booktabs)
+    Latex,
+
     /// Markdown table
     Markdown,
 
+    /// OpenMetrics (Prometheus) text exposition format
+    OpenMetrics,
+
     /// Emacs org-mode tables
     Orgmode,
+
+    /// reStructuredText grid table
+    Rst,
+}
+
+/// Name that identifies a benchmark in exports for continuous benchmarking tools: the
+/// command, followed by the values of all parameters (which are not necessarily part of
+/// the command name)
+fn benchmark_name(result: &BenchmarkResult) -> String {
+    if result.parameters.is_empty() {
+        return result.command.clone();
+    }
+    let parameters: Vec<_> = result
+        .parameters
+        .iter()
+        .map(|(name, value)| format!("{name} = {value}"))
+        .collect();
+    format!("{} ({})", result.command, parameters.join(", "))
+}
+
+/// Names of all benchmarks (see `benchmark_name`). Benchmarks with the same name are
+/// distinguished by their number, e.g. "build #2", such that the names are unique.
+fn benchmark_names(results: &[BenchmarkResult]) -> Vec<String> {
+    let names: Vec<String> = results.iter().map(benchmark_name).collect();
+    names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            if names.iter().filter(|&other| other == name).count() > 1 {
+                format!("{name} #{}", i + 1)
+            } else {
+                name.clone()
+            }
+        })
+        .collect()
 }
 
 /// Interface for different exporters.
@@ -50,6 +128,9 @@ trait Exporter {
         results: &[BenchmarkResult],
         unit: Option<Unit>,
         sort_order: SortOrder,
+        statistic: SummaryStatistic,
+        columns: &[Column],
+        metadata: Option<&Metadata>,
     ) -> Result<Vec<u8>>;
 }
 
@@ -61,6 +142,7 @@ pub enum ExportTarget {
 struct ExporterWithTarget {
     exporter: Box<dyn Exporter>,
     target: ExportTarget,
+    include_metadata: bool,
 }
 
 /// Handles the management of multiple file exporters.
@@ -68,6 +150,13 @@ pub struct ExportManager {
     exporters: Vec<ExporterWithTarget>,
     time_unit: Option<Unit>,
     sort_order: SortOrder,
+    statistic: SummaryStatistic,
+    columns: Vec<Column>,
+    columns_in_summary: bool,
+    metadata: Metadata,
+    metadata_in_markup: bool,
+    regression_threshold: Option<Scalar>,
+    first_is_reference: bool,
 }
 
 impl ExportManager {
@@ -77,11 +166,37 @@ impl ExportManager {
         matches: &ArgMatches,
         time_unit: Option<Unit>,
         sort_order: SortOrder,
+        statistic: SummaryStatistic,
+        metadata: Metadata,
     ) -> Result<Self> {
+        let custom_columns = matches.get_one::<String>("export-columns");
+        let columns = match custom_columns {
+            Some(list) => Column::parse_list(list)?,
+            None => Column::DEFAULT.to_vec(),
+        };
+        let regression_threshold = match matches.get_one::<String>("junit-threshold") {
+            Some(threshold) => {
+                let threshold = threshold
+                    .parse::<Scalar>()
+                    .map_err(|e| OptionsError::FloatParsingError("junit-threshold", e))?;
+                if threshold.is_nan() || threshold < 1.0 {
+                    return Err(OptionsError::InvalidJunitThreshold.into());
+                }
+                Some(threshold)
+            }
+            None => None,
+        };
         let mut export_manager = Self {
             exporters: vec![],
             time_unit,
             sort_order,
+            statistic,
+            columns,
+            columns_in_summary: custom_columns.is_some(),
+            metadata,
+            metadata_in_markup: matches.get_flag("export-metadata"),
+            regression_threshold,
+            first_is_reference: matches.contains_id("reference"),
         };
         {
             let mut add_exporter = |flag, exporttype| -> Result<()> {
@@ -92,9 +207,16 @@ impl ExportManager {
             };
             add_exporter("export-asciidoc", ExportType::Asciidoc)?;
             add_exporter("export-json", ExportType::Json)?;
+            add_exporter("export-junit", ExportType::Junit)?;
             add_exporter("export-csv", ExportType::Csv)?;
+            add_exporter("export-html", ExportType::Html)?;
             add_exporter("export-markdown", ExportType::Markdown)?;
+            add_exporter("export-openmetrics", ExportType::OpenMetrics)?;
             add_exporter("export-orgmode", ExportType::Orgmode)?;
+            add_exporter("export-latex", ExportType::Latex)?;
+            add_exporter("export-rst", ExportType::Rst)?;
+            add_exporter("export-github-benchmark", ExportType::GithubBenchmark)?;
+            add_exporter("export-bencher", ExportType::Bencher)?;
         }
         Ok(export_manager)
     }
@@ -103,14 +225,38 @@ impl ExportManager {
     pub fn add_exporter(&mut self, export_type: ExportType, filename: &str) -> Result<()> {
         let exporter: Box<dyn Exporter> = match export_type {
             ExportType::Asciidoc => Box::<AsciidocExporter>::default(),
+            ExportType::Bencher => Box::<BencherExporter>::default(),
             ExportType::Csv => Box::<CsvExporter>::default(),
+            ExportType::GithubBenchmark => Box::<GithubBenchmarkExporter>::default(),
+            ExportType::Html => Box::<HtmlExporter>::default(),
             ExportType::Json => Box::<JsonExporter>::default(),
+            ExportType::Junit => Box::new(JunitExporter::new(
+                self.regression_threshold,
+                self.first_is_reference,
+            )),
+            ExportType::Latex => Box::<LatexExporter>::default(),
             ExportType::Markdown => Box::<MarkdownExporter>::default(),
+            ExportType::OpenMetrics => Box::<OpenMetricsExporter>::default(),
             ExportType::Orgmode => Box::<OrgmodeExporter>::default(),
+            ExportType::Rst => Box::<RstExporter>::default(),
+        };
+
+        let include_metadata = match export_type {
+            ExportType::Html | ExportType::Json | ExportType::Junit => true,
+            ExportType::Bencher
+            | ExportType::Csv
+            | ExportType::GithubBenchmark
+            | ExportType::OpenMetrics => false,
+            ExportType::Asciidoc
+            | ExportType::Latex
+            | ExportType::Markdown
+            | ExportType::Orgmode
+            | ExportType::Rst => self.metadata_in_markup,
         };
 
         self.exporters.push(ExporterWithTarget {
             exporter,
+            include_metadata,
             target: if filename == "-" {
                 ExportTarget::Stdout
             } else {
@@ -123,6 +269,25 @@ impl ExportManager {
         Ok(())
     }
 
+    /// Information about the system and the hyperfine invocation
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// A table of the given results with the columns that have been selected via
+    /// '--export-columns' for the terminal output (None if no columns have been selected)
+    pub fn summary_table(&self, results: &[BenchmarkResult]) -> Option<String> {
+        if !self.columns_in_summary || results.is_empty() {
+            return None;
+        }
+        let unit = self
+            .time_unit
+            .unwrap_or_else(|| determine_unit_from_results(results));
+        let entries = relative_speed::compute(results, self.sort_order, self.statistic);
+
+        Some(TerminalExporter::default().table_results(&entries, unit, &self.columns))
+    }
+
     /// Write the given results to all Exporters. The 'intermediate' flag specifies
     /// whether this is being called while still performing benchmarks, or if this
     /// is the final call after all benchmarks have been finished. In the former case,
@@ -130,10 +295,17 @@ impl ExportManager {
     /// if a benchmark fails). In the latter case, we only print to stdout targets (in
     /// order not to clutter the output of hyperfine with intermediate results).
     pub fn write_results(&self, results: &[BenchmarkResult], intermediate: bool) -> Result<()> {
+        let metadata = self.metadata.finished_now();
         for e in &self.exporters {
             let content = || {
-                e.exporter
-                    .serialize(results, self.time_unit, self.sort_order)
+                e.exporter.serialize(
+                    results,
+                    self.time_unit,
+                    self.sort_order,
+                    self.statistic,
+                    &self.columns,
+                    e.include_metadata.then_some(&metadata),
+                )
             };
 
             match e.target {
@@ -0,0 +1,124 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::time::Instant;
+
+use serde::*;
+
+use super::Metadata;
+use crate::benchmark::benchmark_result::{BenchmarkResult, Run};
+use crate::benchmark::calibration::Calibration;
+use crate::util::units::Second;
+
+use anyhow::{Context, Result};
+use clap::ArgMatches;
+
+/// A step in the lifecycle of a benchmark session
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    /// The benchmark session has been started
+    SessionStart {
+        metadata: &'a Metadata,
+        commands: Vec<String>,
+    },
+
+    /// The shell spawning time has been measured
+    Calibration(&'a Calibration),
+
+    /// The benchmark of a single command has been started
+    BenchmarkStart { benchmark: usize, command: String },
+
+    /// A warmup or benchmark run has been completed
+    Run {
+        benchmark: usize,
+        #[serde(flatten)]
+        run: &'a Run,
+        time_real: Second,
+        time_user: Second,
+        time_system: Second,
+        exit_code: Option<i32>,
+    },
+
+    /// The benchmark of a single command has been completed
+    BenchmarkEnd {
+        benchmark: usize,
+        result: &'a BenchmarkResult,
+    },
+
+    /// All benchmarks have been completed, or the session has been aborted with an error
+    SessionEnd {
+        end_time: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    /// Time since the start of the session
+    time: Second,
+
+    #[serde(flatten)]
+    event: Event<'a>,
+}
+
+/// Stream of benchmark events in the newline-delimited JSON format ('--export-ndjson').
+/// Every event is written (and flushed) as soon as it happens, such that the progress of
+/// a benchmark session can be followed by other tools.
+pub struct EventStream {
+    writer: Option<RefCell<Box<dyn Write>>>,
+    start: Instant,
+}
+
+impl Default for EventStream {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl EventStream {
+    fn new(writer: Option<Box<dyn Write>>) -> Self {
+        EventStream {
+            writer: writer.map(RefCell::new),
+            start: Instant::now(),
+        }
+    }
+
+    /// Set up the event stream for the target specified in the given ArgMatches. A
+    /// target of "-" refers to the standard output.
+    pub fn from_cli_arguments(matches: &ArgMatches) -> Result<Self> {
+        let writer: Option<Box<dyn Write>> = match matches.get_one::<String>("export-ndjson") {
+            Some(target) if target == "-" => Some(Box::new(io::stdout())),
+            Some(filename) => Some(Box::new(BufWriter::new(
+                File::create(filename)
+                    .with_context(|| format!("Could not create export file '{filename}'"))?,
+            ))),
+            None => None,
+        };
+        Ok(Self::new(writer))
+    }
+
+    /// The start of the benchmark session
+    pub fn start(&self) -> Instant {
+        self.start
+    }
+
+    /// Write a single event to the stream (if enabled)
+    pub fn emit(&self, event: Event) -> Result<()> {
+        let Some(writer) = &self.writer else {
+            return Ok(());
+        };
+
+        let record = Record {
+            time: self.start.elapsed().as_secs_f64(),
+            event,
+        };
+        let mut writer = writer.borrow_mut();
+        serde_json::to_writer(&mut *writer, &record)?;
+        writeln!(writer)?;
+        writer
+            .flush()
+            .context("Could not write benchmark event to NDJSON export")
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+
+use super::markup::Column;
+use super::{Exporter, Metadata};
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::options::{SortOrder, SummaryStatistic};
+use crate::util::units::{Scalar, Unit};
+
+use anyhow::Result;
+
+/// A metric family with one gauge per benchmarked command
+struct Metric {
+    name: &'static str,
+    unit: &'static str,
+    help: &'static str,
+    value: fn(&BenchmarkResult) -> Option<Scalar>,
+}
+
+const METRICS: &[Metric] = &[
+    Metric {
+        name: "hyperfine_mean_seconds",
+        unit: "seconds",
+        help: "Mean wall clock time of the benchmark runs",
+        value: |result| Some(result.mean),
+    },
+    Metric {
+        name: "hyperfine_median_seconds",
+        unit: "seconds",
+        help: "Median wall clock time of the benchmark runs",
+        value: |result| Some(result.median),
+    },
+    Metric {
+        name: "hyperfine_stddev_seconds",
+        unit: "seconds",
+        help: "Standard deviation of the wall clock time of the benchmark runs",
+        value: |result| result.stddev,
+    },
+    Metric {
+        name: "hyperfine_min_seconds",
+        unit: "seconds",
+        help: "Minimum wall clock time of the benchmark runs",
+        value: |result| Some(result.min),
+    },
+    Metric {
+        name: "hyperfine_max_seconds",
+        unit: "seconds",
+        help: "Maximum wall clock time of the benchmark runs",
+        value: |result| Some(result.max),
+    },
+    Metric {
+        name: "hyperfine_user_seconds",
+        unit: "seconds",
+        help: "Mean time spent in user mode",
+        value: |result| Some(result.user),
+    },
+    Metric {
+        name: "hyperfine_system_seconds",
+        unit: "seconds",
+        help: "Mean time spent in kernel mode",
+        value: |result| Some(result.system),
+    },
+    Metric {
+        name: "hyperfine_memory_usage_bytes",
+        unit: "bytes",
+        help: "Maximum memory usage of the benchmarked command over all runs",
+        value: |result| {
+            result
+                .memory_usage_byte
+                .as_ref()
+                .and_then(|memory| memory.iter().max())
+                .map(|&max| max as Scalar)
+        },
+    },
+];
+
+/// Exports the results in the OpenMetrics text exposition format (which can also be read
+/// by Prometheus, e.g. via the textfile collector of the node exporter or a Pushgateway).
+/// All times are given in seconds.
+#[derive(Default)]
+pub struct OpenMetricsExporter {}
+
+impl Exporter for OpenMetricsExporter {
+    fn serialize(
+        &self,
+        results: &[BenchmarkResult],
+        _unit: Option<Unit>,
+        _sort_order: SortOrder,
+        _statistic: SummaryStatistic,
+        _columns: &[Column],
+        _metadata: Option<&Metadata>,
+    ) -> Result<Vec<u8>> {
+        let label_names = label_names(results);
+        let mut labels: Vec<String> = results
+            .iter()
+            .map(|result| labels(result, &label_names))
+            .collect();
+
+        // Every series of a metric family needs a unique label set
+        let unique: HashSet<&String> = labels.iter().collect();
+        if unique.len() < labels.len() {
+            for (i, labels) in labels.iter_mut().enumerate() {
+                labels.push_str(&format!(",benchmark=\"{}\"", i + 1));
+            }
+        }
+
+        let mut output = String::new();
+        for metric in METRICS {
+            writeln!(output, "# HELP {} {}.", metric.name, metric.help)?;
+            writeln!(output, "# TYPE {} gauge", metric.name)?;
+            writeln!(output, "# UNIT {} {}", metric.name, metric.unit)?;
+            for (result, labels) in results.iter().zip(&labels) {
+                if let Some(value) = (metric.value)(result) {
+                    writeln!(
+                        output,
+                        "{}{{{labels}}} {}",
+                        metric.name,
+                        format_value(value)
+                    )?;
+                }
+            }
+        }
+        output.push_str("# EOF\n");
+
+        Ok(output.into_bytes())
+    }
+}
+
+/// The label set of a command: its name and the values of all parameters
+fn labels(result: &BenchmarkResult, label_names: &BTreeMap<&str, String>) -> String {
+    let mut labels = format!("command=\"{}\"", escape_label_value(&result.command));
+    for (name, value) in &result.parameters {
+        labels.push_str(&format!(
+            ",{}=\"{}\"",
+            label_names[name.as_str()],
+            escape_label_value(value)
+        ));
+    }
+    labels
+}
+
+/// The label names of all parameters. Parameter names which map to the same label name
+/// (like 'a-b' and 'a_b') are distinguished by a numeric suffix.
+fn label_names(results: &[BenchmarkResult]) -> BTreeMap<&str, String> {
+    let mut label_names = BTreeMap::new();
+    let mut used = HashSet::new();
+    for name in results.iter().flat_map(|result| result.parameters.keys()) {
+        if label_names.contains_key(name.as_str()) {
+            continue;
+        }
+        let base = label_name(name);
+        let mut label = base.clone();
+        for suffix in 2.. {
+            if !used.contains(&label) {
+                break;
+            }
+            label = format!("{base}_{suffix}");
+        }
+        used.insert(label.clone());
+        label_names.insert(name.as_str(), label);
+    }
+    label_names
+}
+
+/// Turn a parameter name into a valid label name (`[a-zA-Z_][a-zA-Z0-9_]*`) that does not
+/// clash with the 'command' and 'benchmark' labels or the names reserved for internal use
+/// ('__' prefix).
+fn label_name(name: &str) -> String {
+    let mut label: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if label.is_empty()
+        || label.starts_with(|c: char| c.is_ascii_digit())
+        || label.starts_with("__")
+        || label == "command"
+        || label == "benchmark"
+    {
+        label.insert_str(0, "parameter_");
+    }
+    label
+}
+
+/// Escape backslashes, double quotes and line feeds in a label value
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_value(value: Scalar) -> String {
+    if value.is_nan() {
+        "NaN".into()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.into()
+    } else {
+        value.to_string()
+    }
+}
+
+#[test]
+fn test_openmetrics_label_escaping() {
+    assert_eq!(label_name("threads"), "threads");
+    assert_eq!(label_name("num-threads"), "num_threads");
+    assert_eq!(label_name("1st"), "parameter_1st");
+    assert_eq!(label_name("command"), "parameter_command");
+    assert_eq!(label_name("benchmark"), "parameter_benchmark");
+    assert_eq!(label_name("__name__"), "parameter___name__");
+    assert_eq!(
+        escape_label_value("echo \"a\\b\"\nc"),
+        "echo \\\"a\\\\b\\\"\\nc"
+    );
+}
+
+#[test]
+fn test_openmetrics_export() {
+    let mut parameters = BTreeMap::new();
+    parameters.insert(String::from("num-threads"), String::from("4"));
+    let results = [
+        BenchmarkResult {
+            command: String::from("make -j 4"),
+            command_with_unused_parameters: String::from("make -j 4"),
+            mean: 1.5,
+            stddev: Some(0.25),
+            median: 1.25,
+            user: 4.0,
+            system: 0.5,
+            min: 1.0,
+            max: 2.0,
+            memory_usage_byte: Some(vec![1024, 4096, 2048]),
+            parameters,
+            ..Default::default()
+        },
+        BenchmarkResult {
+            command: String::from("echo \"done\""),
+            command_with_unused_parameters: String::from("echo \"done\""),
+            mean: 0.001,
+            median: 0.001,
+            min: 0.001,
+            max: 0.001,
+            ..Default::default()
+        },
+    ];
+
+    let output = OpenMetricsExporter::default()
+        .serialize(
+            &results,
+            Some(Unit::MilliSecond),
+            SortOrder::Command,
+            SummaryStatistic::Mean,
+            &[],
+            None,
+        )
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.starts_with(
+        "# HELP hyperfine_mean_seconds Mean wall clock time of the benchmark runs.\n\
+         # TYPE hyperfine_mean_seconds gauge\n\
+         # UNIT hyperfine_mean_seconds seconds\n\
+         hyperfine_mean_seconds{command=\"make -j 4\",num_threads=\"4\"} 1.5\n\
+         hyperfine_mean_seconds{command=\"echo \\\"done\\\"\"} 0.001\n"
+    ));
+    assert!(output.contains(
+        "hyperfine_stddev_seconds{command=\"make -j 4\",num_threads=\"4\"} 0.25\n\
+         # HELP hyperfine_min_seconds Minimum wall clock time of the benchmark runs.\n"
+    ));
+    assert!(output.contains(
+        "hyperfine_memory_usage_bytes{command=\"make -j 4\",num_threads=\"4\"} 4096\n# EOF\n"
+    ));
+    assert!(output.ends_with("# EOF\n"));
+}
+
+/// Label names and label sets have to be unique
+#[test]
+fn test_openmetrics_label_collisions() {
+    let result = |command: &str, parameters: &[(&str, &str)]| BenchmarkResult {
+        command: String::from(command),
+        command_with_unused_parameters: String::from(command),
+        mean: 1.0,
+        parameters: parameters
+            .iter()
+            .map(|&(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+        ..Default::default()
+    };
+    let export = |results: &[BenchmarkResult]| {
+        let output = OpenMetricsExporter::default()
+            .serialize(
+                results,
+                None,
+                SortOrder::Command,
+                SummaryStatistic::Mean,
+                &[],
+                None,
+            )
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    };
+
+    let output = export(&[result("ls", &[("a-b", "1"), ("a_b", "2"), ("a_b_2", "3")])]);
+    assert!(output.contains(
+        "hyperfine_mean_seconds{command=\"ls\",a_b=\"1\",a_b_2=\"2\",a_b_2_2=\"3\"} 1\n"
+    ));
+
+    let output = export(&[result("ls", &[]), result("ls", &[])]);
+    assert!(output.contains(
+        "hyperfine_mean_seconds{command=\"ls\",benchmark=\"1\"} 1\n\
+         hyperfine_mean_seconds{command=\"ls\",benchmark=\"2\"} 1\n"
+    ));
+}
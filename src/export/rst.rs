@@ -0,0 +1,101 @@
+use super::markup::{column_widths, pad_cells, Alignment};
+use crate::export::markup::MarkupExporter;
+
+/// Exports reStructuredText grid tables (e.g. for Sphinx documentation)
+#[derive(Default)]
+pub struct RstExporter {}
+
+impl MarkupExporter for RstExporter {
+    fn table(
+        &self,
+        cells_alignment: &[Alignment],
+        headers: &[String],
+        rows: &[Vec<String>],
+    ) -> String {
+        let widths = column_widths(headers, rows);
+
+        let line = |c: &str| {
+            let segments: Vec<_> = widths.iter().map(|width| c.repeat(width + 2)).collect();
+            format!("+{}+\n", segments.join("+"))
+        };
+        let row = |cells: &[String]| {
+            let padded = pad_cells(cells, &widths, cells_alignment);
+            self.table_row(&padded.iter().map(|c| c.as_str()).collect::<Vec<_>>())
+        };
+
+        let mut table = line("-");
+        table.push_str(&row(headers));
+        table.push_str(&line("="));
+        for cells in rows {
+            table.push_str(&row(cells));
+            table.push_str(&line("-"));
+        }
+
+        table
+    }
+
+    fn table_row(&self, cells: &[&str]) -> String {
+        format!("| {} |\n", cells.join(" | "))
+    }
+
+    fn table_divider(&self, _cell_aligmnents: &[Alignment]) -> String {
+        // The lines between the rows depend on the column widths (see `table`)
+        "".to_string()
+    }
+
+    fn command(&self, cmd: &str) -> String {
+        // Backslash escapes are not supported in ``inline literals``, but in roles
+        format!(":literal:`{cmd}`")
+    }
+
+    fn escape(&self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            if matches!(c, '\\' | '`' | '*' | '_' | '|') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+}
+
+/// Check reStructuredText grid table formatting
+#[test]
+fn test_rst_exporter_table() {
+    let exporter = RstExporter::default();
+
+    let actual = exporter.table(
+        &[Alignment::Left, Alignment::Right],
+        &["Command".to_string(), "Mean [ms]".to_string()],
+        &[
+            vec![
+                ":literal:`sleep 0.1`".to_string(),
+                "100.0 ± 0.1".to_string(),
+            ],
+            vec![":literal:`a`".to_string(), "1.0".to_string()],
+        ],
+    );
+    let expect = "\
++----------------------+-------------+
+| Command              |   Mean [ms] |
++======================+=============+
+| :literal:`sleep 0.1` | 100.0 ± 0.1 |
++----------------------+-------------+
+| :literal:`a`         |         1.0 |
++----------------------+-------------+
+";
+
+    assert_eq!(expect, actual);
+}
+
+/// Check escaping of inline markup characters
+#[test]
+fn test_rst_exporter_escape() {
+    let exporter = RstExporter::default();
+
+    assert_eq!(
+        exporter.command(&exporter.escape("ls *_test.txt | grep `echo \\d`")),
+        ":literal:`ls \\*\\_test.txt \\| grep \\`echo \\\\d\\``"
+    );
+}
@@ -0,0 +1,72 @@
+use colored::*;
+
+use super::markup::{column_widths, pad_cells, Alignment};
+use crate::export::markup::MarkupExporter;
+
+/// Formats the results as a plain table with aligned columns for the terminal output
+#[derive(Default)]
+pub struct TerminalExporter {}
+
+impl MarkupExporter for TerminalExporter {
+    fn table(
+        &self,
+        cells_alignment: &[Alignment],
+        headers: &[String],
+        rows: &[Vec<String>],
+    ) -> String {
+        let widths = column_widths(headers, rows);
+        let line = |cells: &[String]| pad_cells(cells, &widths, cells_alignment).join("  ");
+
+        let mut table = format!("  {}\n", line(headers).trim_end().bold());
+        for cells in rows {
+            let cells = line(cells);
+            table.push_str(&self.table_row(&[cells.trim_end()]));
+        }
+
+        table
+    }
+
+    fn table_row(&self, cells: &[&str]) -> String {
+        format!("  {}\n", cells.join("  "))
+    }
+
+    fn table_divider(&self, _cell_aligmnents: &[Alignment]) -> String {
+        "".to_string()
+    }
+
+    fn command(&self, cmd: &str) -> String {
+        cmd.to_string()
+    }
+
+    fn escape(&self, text: &str) -> String {
+        text.to_string()
+    }
+}
+
+/// Check the alignment of the columns
+#[test]
+fn test_terminal_table() {
+    let exporter = TerminalExporter::default();
+
+    let actual = exporter.table(
+        &[Alignment::Left, Alignment::Right, Alignment::Left],
+        &[
+            "Command".to_string(),
+            "Median [ms]".to_string(),
+            "size".to_string(),
+        ],
+        &[
+            vec![
+                "sleep 0.1".to_string(),
+                "100.0".to_string(),
+                "10".to_string(),
+            ],
+            vec!["a".to_string(), "1.0".to_string(), "".to_string()],
+        ],
+    );
+    let expect = "  Command    Median [ms]  size\n  \
+                    sleep 0.1        100.0  10\n  \
+                    a                  1.0\n";
+
+    assert_eq!(expect, console::strip_ansi_codes(&actual));
+}
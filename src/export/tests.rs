@@ -1,18 +1,40 @@
 use super::Exporter;
-use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::benchmark::benchmark_result::{BenchmarkResult, Percentiles};
 use crate::export::asciidoc::AsciidocExporter;
+use crate::export::markdown::MarkdownExporter;
+use crate::export::markup::Column;
 use crate::export::orgmode::OrgmodeExporter;
+use crate::options::{SortOrder, SummaryStatistic};
 use crate::util::units::Unit;
-use crate::{export::markdown::MarkdownExporter, options::SortOrder};
-use std::collections::BTreeMap;
 
 fn get_output<E: Exporter + Default>(
     results: &[BenchmarkResult],
     unit: Option<Unit>,
     sort_order: SortOrder,
+) -> String {
+    get_output_with_columns::<E>(results, unit, sort_order, &Column::DEFAULT)
+}
+
+fn get_output_with_columns<E: Exporter + Default>(
+    results: &[BenchmarkResult],
+    unit: Option<Unit>,
+    sort_order: SortOrder,
+    columns: &[Column],
 ) -> String {
     let exporter = E::default();
-    String::from_utf8(exporter.serialize(results, unit, sort_order).unwrap()).unwrap()
+    String::from_utf8(
+        exporter
+            .serialize(
+                results,
+                unit,
+                sort_order,
+                SummaryStatistic::Mean,
+                columns,
+                None,
+            )
+            .unwrap(),
+    )
+    .unwrap()
 }
 
 /// Ensure the makrup output includes the table header and the multiple
@@ -35,9 +57,8 @@ fn test_markup_export_auto_ms() {
             min: 0.1023,
             max: 0.1080,
             times: Some(vec![0.1, 0.1, 0.1]),
-            memory_usage_byte: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
-            parameters: BTreeMap::new(),
+            ..Default::default()
         },
         BenchmarkResult {
             command: String::from("sleep 2"),
@@ -50,9 +71,8 @@ fn test_markup_export_auto_ms() {
             min: 2.0020,
             max: 2.0080,
             times: Some(vec![2.0, 2.0, 2.0]),
-            memory_usage_byte: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
-            parameters: BTreeMap::new(),
+            ..Default::default()
         },
     ];
 
@@ -110,9 +130,8 @@ fn test_markup_export_auto_s() {
             min: 2.0020,
             max: 2.0080,
             times: Some(vec![2.0, 2.0, 2.0]),
-            memory_usage_byte: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
-            parameters: BTreeMap::new(),
+            ..Default::default()
         },
         BenchmarkResult {
             command: String::from("sleep 0.1"),
@@ -125,9 +144,8 @@ fn test_markup_export_auto_s() {
             min: 0.1023,
             max: 0.1080,
             times: Some(vec![0.1, 0.1, 0.1]),
-            memory_usage_byte: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
-            parameters: BTreeMap::new(),
+            ..Default::default()
         },
     ];
 
@@ -185,9 +203,8 @@ fn test_markup_export_manual_ms() {
             min: 2.0020,
             max: 2.0080,
             times: Some(vec![2.0, 2.0, 2.0]),
-            memory_usage_byte: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
-            parameters: BTreeMap::new(),
+            ..Default::default()
         },
         BenchmarkResult {
             command: String::from("sleep 0.1"),
@@ -200,9 +217,8 @@ fn test_markup_export_manual_ms() {
             min: 0.1023,
             max: 0.1080,
             times: Some(vec![0.1, 0.1, 0.1]),
-            memory_usage_byte: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
-            parameters: BTreeMap::new(),
+            ..Default::default()
         },
     ];
 
@@ -259,9 +275,8 @@ fn test_markup_export_manual_s() {
             min: 2.0020,
             max: 2.0080,
             times: Some(vec![2.0, 2.0, 2.0]),
-            memory_usage_byte: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
-            parameters: BTreeMap::new(),
+            ..Default::default()
         },
         BenchmarkResult {
             command: String::from("sleep 0.1"),
@@ -274,9 +289,8 @@ fn test_markup_export_manual_s() {
             min: 0.1023,
             max: 0.1080,
             times: Some(vec![0.1, 0.1, 0.1]),
-            memory_usage_byte: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
-            parameters: BTreeMap::new(),
+            ..Default::default()
         },
     ];
 
@@ -317,3 +331,150 @@ fn test_markup_export_manual_s() {
     |===
     "#);
 }
+
+/// Percentiles can be selected as columns. If a percentile has not been computed
+/// during the benchmark, it is derived from the individual run times.
+#[test]
+fn test_markup_export_percentile_columns() {
+    let results = [BenchmarkResult {
+        command: String::from("sleep 0.1"),
+        command_with_unused_parameters: String::from("sleep 0.1"),
+        mean: 0.1057,
+        stddev: Some(0.0016),
+        median: 0.1057,
+        user: 0.0009,
+        system: 0.0011,
+        min: 0.1023,
+        max: 0.1080,
+        percentiles: Percentiles(vec![(50.0, 0.1050), (95.0, 0.1077)]),
+        times: Some(vec![0.1023, 0.1050, 0.1080]),
+        exit_codes: vec![Some(0), Some(0), Some(0)],
+        ..Default::default()
+    }];
+
+    let columns = Column::parse_list("command,p95,p0,relative").unwrap();
+
+    insta::assert_snapshot!(get_output_with_columns::<MarkdownExporter>(&results, Some(Unit::MilliSecond), SortOrder::Command, &columns), @r#"
+    | Command | p95 [ms] | p0 [ms] | Relative |
+    |:---|---:|---:|---:|
+    | `sleep 0.1` | 107.7 | 102.3 | 1.00 |
+    "#);
+}
+
+/// Columns for the median, the user and system times, the memory usage and the values of
+/// parameters. Parameters that are not set for a command result in empty cells.
+#[test]
+fn test_markup_export_selected_columns() {
+    let results = [
+        BenchmarkResult {
+            command: String::from("make -j 4"),
+            command_with_unused_parameters: String::from("make -j 4"),
+            mean: 1.5,
+            stddev: Some(0.25),
+            median: 1.25,
+            user: 4.0,
+            system: 0.5,
+            min: 1.0,
+            max: 2.0,
+            memory_usage_byte: Some(vec![1048576, 3670016, 2097152]),
+            parameters: vec![(String::from("threads"), String::from("4"))]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        },
+        BenchmarkResult {
+            command: String::from("make"),
+            command_with_unused_parameters: String::from("make"),
+            mean: 3.0,
+            median: 3.0,
+            user: 2.75,
+            system: 0.25,
+            min: 3.0,
+            max: 3.0,
+            ..Default::default()
+        },
+    ];
+
+    let columns =
+        Column::parse_list("command,median,user,system,memory,parameter_threads").unwrap();
+
+    insta::assert_snapshot!(get_output_with_columns::<MarkdownExporter>(&results, Some(Unit::Second), SortOrder::Command, &columns), @r#"
+    | Command | Median [s] | User [s] | System [s] | Memory [MiB] | threads |
+    |:---|---:|---:|---:|---:|:---|
+    | `make -j 4` | 1.250 | 4.000 | 0.500 | 3.5 | 4 |
+    | `make` | 3.000 | 2.750 | 0.250 |  |  |
+    "#);
+}
+
+#[test]
+fn test_parse_export_columns() {
+    assert_eq!(
+        Column::parse_list("command, mean,p99.9").unwrap(),
+        vec![Column::Command, Column::Mean, Column::Percentile(99.9)]
+    );
+    assert_eq!(
+        Column::parse_list("median,user,system,memory,parameter_threads").unwrap(),
+        vec![
+            Column::Median,
+            Column::User,
+            Column::System,
+            Column::Memory,
+            Column::Parameter("threads".into())
+        ]
+    );
+    assert!(Column::parse_list("command,p101").is_err());
+    assert!(Column::parse_list("command,parameter_").is_err());
+    assert!(Column::parse_list("command,foo").is_err());
+}
+
+/// With metadata, the markup exports start with a table of system information.
+#[test]
+fn test_markup_export_metadata_header() {
+    use crate::export::metadata;
+
+    let results = [BenchmarkResult {
+        command: String::from("sleep 0.1"),
+        command_with_unused_parameters: String::from("sleep 0.1"),
+        mean: 0.1057,
+        stddev: Some(0.0016),
+        median: 0.1057,
+        user: 0.0009,
+        system: 0.0011,
+        min: 0.1023,
+        max: 0.1080,
+        times: Some(vec![0.1023, 0.1050, 0.1080]),
+        exit_codes: vec![Some(0), Some(0), Some(0)],
+        ..Default::default()
+    }];
+
+    let output = MarkdownExporter::default()
+        .serialize(
+            &results,
+            Some(Unit::MilliSecond),
+            SortOrder::Command,
+            SummaryStatistic::Mean,
+            &Column::DEFAULT,
+            Some(&metadata::example()),
+        )
+        .unwrap();
+
+    insta::assert_snapshot!(String::from_utf8(output).unwrap(), @r#"
+    | Property | Value |
+    |:---|:---|
+    | hyperfine version | 1.20.0 |
+    | Host | benchmark-host |
+    | System | Debian GNU/Linux 12 (bookworm), kernel 6.1.0-18-amd64 (x86_64) |
+    | CPU | AMD Ryzen 7 5800X, 16 cores |
+    | Memory | 32.0 GiB |
+    | CPU governor | performance |
+    | Load average | 0.50, 0.25, 0.75 |
+    | Shell | sh |
+    | Invocation | hyperfine 'sleep 0.1' |
+    | Started | 2024-03-01T12:00:00Z |
+    | Finished | 2024-03-01T12:00:05Z |
+
+    | Command | Mean [ms] | Min [ms] | Max [ms] | Relative |
+    |:---|---:|---:|---:|---:|
+    | `sleep 0.1` | 105.7 ± 1.6 | 102.3 | 108.0 | 1.00 |
+    "#);
+}
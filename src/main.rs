@@ -8,7 +8,7 @@ use std::env;
 use benchmark::scheduler::Scheduler;
 use cli::get_cli_arguments;
 use command::Commands;
-use export::ExportManager;
+use export::{import_results, EventStream, ExportManager, Metadata};
 use options::Options;
 
 use anyhow::Result;
@@ -34,16 +34,35 @@ fn run() -> Result<()> {
     let cli_arguments = get_cli_arguments(env::args_os());
     let mut options = Options::from_cli_arguments(&cli_arguments)?;
     let commands = Commands::from_cli_arguments(&cli_arguments)?;
+
+    let (imported_results, metadata) = match cli_arguments.get_one::<String>("from-json") {
+        Some(filename) => {
+            let (results, metadata) = import_results(filename)?;
+            let metadata = metadata.unwrap_or_else(|| Metadata::collect(&options.executor_kind));
+            (Some(results), metadata)
+        }
+        None => (None, Metadata::collect(&options.executor_kind)),
+    };
     let export_manager = ExportManager::from_cli_arguments(
         &cli_arguments,
         options.time_unit,
         options.sort_order_exports,
+        options.statistic,
+        metadata,
     )?;
+    let events = EventStream::from_cli_arguments(&cli_arguments)?;
 
-    options.validate_against_command_list(&commands)?;
+    if imported_results.is_none() {
+        options.validate_against_command_list(&commands)?;
+    }
 
-    let mut scheduler = Scheduler::new(&commands, &options, &export_manager);
-    scheduler.run_benchmarks()?;
+    let mut scheduler = Scheduler::new(&commands, &options, &export_manager, &events);
+    if let Some(results) = imported_results {
+        scheduler.import_results(results)?;
+    } else {
+        scheduler.run_benchmarks()?;
+    }
+    scheduler.print_summary_table();
     scheduler.print_relative_speed_comparison();
     scheduler.final_export()?;
 
@@ -1,15 +1,19 @@
 use std::fs::File;
 use std::io::IsTerminal;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::{cmp, env, fmt, io};
 
-use anyhow::ensure;
+use anyhow::{ensure, Context};
 use clap::ArgMatches;
+use serde::{Deserialize, Serialize};
 
-use crate::command::Commands;
+use crate::benchmark::benchmark_result::Percentiles;
+use crate::benchmark::executor::BenchmarkIteration;
+use crate::benchmark::output_check::OutputDigest;
+use crate::command::{parse_environment, Command as BenchmarkCommand, Commands};
 use crate::error::OptionsError;
-use crate::util::units::{Second, Unit};
+use crate::util::units::{Scalar, Second, Unit};
 
 use anyhow::Result;
 
@@ -104,6 +108,64 @@ pub enum SortOrder {
     MeanTime,
 }
 
+/// How to handle statistical outliers in the measured run times
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum OutlierPolicy {
+    /// Keep all measurements (only warn about outliers)
+    #[default]
+    Keep,
+
+    /// Exclude outliers from the summary statistics
+    Remove,
+
+    /// Clamp outliers to the range of the remaining measurements
+    Winsorize,
+}
+
+/// The statistic of the run times that is used to compare and sort benchmarks
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum SummaryStatistic {
+    /// Arithmetic mean
+    #[default]
+    Mean,
+
+    /// Median
+    Median,
+
+    /// Minimum
+    Min,
+
+    /// A percentile (between 0 and 100)
+    Percentile(Scalar),
+}
+
+impl SummaryStatistic {
+    /// Parse a statistic like "mean", "median", "min" or "p95"
+    pub fn parse_from_str<'a>(s: &str) -> Result<Self, OptionsError<'a>> {
+        match s {
+            "mean" => Ok(SummaryStatistic::Mean),
+            "median" => Ok(SummaryStatistic::Median),
+            "min" => Ok(SummaryStatistic::Min),
+            _ => Percentiles::parse_name(s)
+                .map(SummaryStatistic::Percentile)
+                .ok_or_else(|| OptionsError::UnknownStatistic(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for SummaryStatistic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SummaryStatistic::Mean => write!(f, "mean"),
+            SummaryStatistic::Median => write!(f, "median"),
+            SummaryStatistic::Min => write!(f, "min"),
+            SummaryStatistic::Percentile(p) => write!(f, "p{p}"),
+        }
+    }
+}
+
 /// Bounds for the number of benchmark runs
 pub struct RunBounds {
     /// Minimum number of benchmark runs
@@ -125,22 +187,161 @@ pub enum CommandInputPolicy {
     #[default]
     Null,
 
-    /// Read input from a file
-    File(PathBuf),
+    /// Read input from a file (as a seekable file descriptor)
+    File(InputSource),
+
+    /// Stream the content of a file through a pipe
+    Pipe(InputSource),
 }
 
-impl CommandInputPolicy {
-    pub fn get_stdin(&self) -> io::Result<Stdio> {
-        let stream: Stdio = match self {
-            CommandInputPolicy::Null => Stdio::null(),
+/// The file(s) that are used as input for the benchmarked commands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The same file for every iteration
+    Path(PathBuf),
+
+    /// A path template (with a `template:` prefix) that can contain `$HYPERFINE_ITERATION`
+    /// and `{param}` placeholders
+    Template(String),
+
+    /// Cycle through the files in a directory (in lexicographic order)
+    Directory(Vec<PathBuf>),
+}
+
+impl InputSource {
+    /// Parse the argument to `--input` (without a potential `pipe:` prefix)
+    fn from_str<'a>(path_str: &str) -> Result<Self, OptionsError<'a>> {
+        if let Some(template) = path_str.strip_prefix("template:") {
+            return Ok(InputSource::Template(template.to_string()));
+        }
+
+        let path = PathBuf::from(path_str);
+        if path.is_dir() {
+            let mut files: Vec<PathBuf> = path
+                .read_dir()
+                .map_err(|_| OptionsError::StdinDataFileDoesNotExist(path_str.to_string()))?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.is_file())
+                .collect();
+            if files.is_empty() {
+                return Err(OptionsError::EmptyInputDirectory(path_str.to_string()));
+            }
+            files.sort();
+            Ok(InputSource::Directory(files))
+        } else if path.exists() {
+            Ok(InputSource::Path(path))
+        } else {
+            Err(OptionsError::StdinDataFileDoesNotExist(
+                path_str.to_string(),
+            ))
+        }
+    }
 
-            CommandInputPolicy::File(path) => {
-                let file: File = File::open(path)?;
-                Stdio::from(file)
+    /// The input file for the given iteration of a command. Returns `None` if the input
+    /// depends on the iteration, but the command does not run as part of a benchmark
+    /// (e.g. a `--prepare` command).
+    fn path_for(
+        &self,
+        command: &BenchmarkCommand<'_>,
+        iteration: &BenchmarkIteration,
+    ) -> Option<PathBuf> {
+        match (self, iteration) {
+            (InputSource::Path(path), _) => Some(path.clone()),
+            (_, BenchmarkIteration::NonBenchmarkRun) => None,
+            (InputSource::Template(template), _) => {
+                let value = iteration.to_env_var_value().unwrap_or_default();
+                let path = template.replace("$HYPERFINE_ITERATION", &value);
+                Some(PathBuf::from(command.replace_parameters_in(&path)))
             }
+            (
+                InputSource::Directory(files),
+                BenchmarkIteration::Warmup(i) | BenchmarkIteration::Benchmark(i),
+            ) => Some(files[*i as usize % files.len()].clone()),
+        }
+    }
+}
+
+impl CommandInputPolicy {
+    /// Parse the argument to `--input`
+    fn from_str<'a>(value: &str) -> Result<Self, OptionsError<'a>> {
+        Ok(match value {
+            "null" => CommandInputPolicy::Null,
+            _ => match value.strip_prefix("pipe:") {
+                Some(path_str) => CommandInputPolicy::Pipe(InputSource::from_str(path_str)?),
+                None => CommandInputPolicy::File(InputSource::from_str(value)?),
+            },
+        })
+    }
+
+    /// The stdin for the given iteration of a command. In pipe mode, this also returns the
+    /// file that needs to be streamed to the process.
+    pub fn get_stdin(
+        &self,
+        command: &BenchmarkCommand<'_>,
+        iteration: &BenchmarkIteration,
+    ) -> Result<(Stdio, Option<File>)> {
+        let (source, pipe) = match self {
+            CommandInputPolicy::Null => return Ok((Stdio::null(), None)),
+            CommandInputPolicy::File(source) => (source, false),
+            CommandInputPolicy::Pipe(source) => (source, true),
+        };
+
+        let Some(path) = source.path_for(command, iteration) else {
+            return Ok((Stdio::null(), None));
         };
+        let file = File::open(&path)
+            .with_context(|| format!("Failed to open input file '{}'", path.display()))?;
+
+        Ok(if pipe {
+            (Stdio::piped(), Some(file))
+        } else {
+            (Stdio::from(file), None)
+        })
+    }
+}
+
+/// How to handle a single output stream (stdout or stderr) of benchmarked commands
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum StreamPolicy {
+    /// Redirect the stream to the null device
+    #[default]
+    Null,
+
+    /// Feed the stream through a pipe before discarding it
+    Pipe,
+
+    /// Redirect the stream to a file
+    File(PathBuf),
+
+    /// Show the stream on the terminal
+    Inherit,
+
+    /// Feed the stream through a pipe and compute its digest, in order to check it
+    /// (only supported for stdout)
+    Digest,
+}
+
+impl StreamPolicy {
+    /// Parse the argument to `--stdout` or `--stderr`
+    fn from_str<'a>(value: &str) -> Result<Self, OptionsError<'a>> {
+        Ok(match value {
+            "null" => StreamPolicy::Null,
+            "pipe" => StreamPolicy::Pipe,
+            "inherit" => StreamPolicy::Inherit,
+            _ => match value.strip_prefix("file:") {
+                Some(path) if !path.is_empty() => StreamPolicy::File(PathBuf::from(path)),
+                _ => return Err(OptionsError::UnknownStreamPolicy(value.to_string())),
+            },
+        })
+    }
 
-        Ok(stream)
+    fn get_stdio(&self) -> io::Result<Stdio> {
+        Ok(match self {
+            StreamPolicy::Null => Stdio::null(),
+            StreamPolicy::Pipe | StreamPolicy::Digest => Stdio::piped(),
+            StreamPolicy::File(path) => File::create(path)?.into(),
+            StreamPolicy::Inherit => Stdio::inherit(),
+        })
     }
 }
 
@@ -159,22 +360,145 @@ pub enum CommandOutputPolicy {
 
     /// Show command output on the terminal
     Inherit,
+
+    /// Separate policies for stdout and stderr
+    Streams {
+        stdout: StreamPolicy,
+        stderr: StreamPolicy,
+    },
+
+    /// Write stdout and stderr of every benchmark run to separate files. The path template
+    /// can contain `{command_index}`, `{command_name}`, `{iteration}` and `{stream}` placeholders.
+    Template(String),
 }
 
 impl CommandOutputPolicy {
-    pub fn get_stdout_stderr(&self) -> io::Result<(Stdio, Stdio)> {
+    /// Placeholders that turn an `--output` path into a template
+    const TEMPLATE_PLACEHOLDERS: [&'static str; 4] = [
+        "{command_index}",
+        "{command_name}",
+        "{iteration}",
+        "{stream}",
+    ];
+
+    /// Resolve the command-specific placeholders (including parameters) of a path template.
+    /// Other policies are returned unchanged.
+    pub fn for_command(&self, number: usize, command: &BenchmarkCommand<'_>) -> Self {
+        match self {
+            CommandOutputPolicy::Template(template) => {
+                let name: String = command
+                    .get_name()
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect();
+                let template = template
+                    .replace("{command_index}", &(number + 1).to_string())
+                    .replace("{command_name}", &name);
+                CommandOutputPolicy::Template(command.replace_parameters_in(&template))
+            }
+            policy => policy.clone(),
+        }
+    }
+
+    /// Whether the output is shown on the terminal
+    pub fn shows_output(&self) -> bool {
+        match self {
+            CommandOutputPolicy::Inherit => true,
+            CommandOutputPolicy::Streams { stdout, stderr } => {
+                *stdout == StreamPolicy::Inherit || *stderr == StreamPolicy::Inherit
+            }
+            _ => false,
+        }
+    }
+
+    /// The policy that is used for runs whose stdout is checked, or `None` if stdout is
+    /// not captured by this policy
+    pub fn for_output_check(&self) -> Option<Self> {
+        let stderr = match self {
+            CommandOutputPolicy::Null | CommandOutputPolicy::Pipe => StreamPolicy::Null,
+            CommandOutputPolicy::Streams {
+                stdout: StreamPolicy::Null | StreamPolicy::Pipe | StreamPolicy::Digest,
+                stderr,
+            } => stderr.clone(),
+            _ => return None,
+        };
+        Some(CommandOutputPolicy::Streams {
+            stdout: StreamPolicy::Digest,
+            stderr,
+        })
+    }
+
+    /// Whether the digest of stdout should be computed
+    pub fn digests_stdout(&self) -> bool {
+        matches!(
+            self,
+            CommandOutputPolicy::Streams {
+                stdout: StreamPolicy::Digest,
+                ..
+            }
+        )
+    }
+
+    /// Create the file for the given stream of a benchmark run
+    fn create_template_file(template: &str, iteration: &str, stream: &str) -> io::Result<File> {
+        let path = PathBuf::from(
+            template
+                .replace("{iteration}", iteration)
+                .replace("{stream}", stream),
+        );
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        File::create(path)
+    }
+
+    pub fn get_stdout_stderr(&self, iteration: &BenchmarkIteration) -> io::Result<(Stdio, Stdio)> {
         let streams = match self {
             CommandOutputPolicy::Null => (Stdio::null(), Stdio::null()),
 
             // Typically only stdout is performance-relevant, so just pipe that
             CommandOutputPolicy::Pipe => (Stdio::piped(), Stdio::null()),
 
+            CommandOutputPolicy::Streams {
+                stdout: StreamPolicy::File(stdout_path),
+                stderr: StreamPolicy::File(stderr_path),
+            } if stdout_path == stderr_path => {
+                // Share one handle, such that the streams do not overwrite each other
+                let file = File::create(stdout_path)?;
+                (file.try_clone()?.into(), file.into())
+            }
+
+            CommandOutputPolicy::Streams { stdout, stderr } => {
+                (stdout.get_stdio()?, stderr.get_stdio()?)
+            }
+
             CommandOutputPolicy::File(path) => {
                 let file = File::create(path)?;
                 (file.into(), Stdio::null())
             }
 
             CommandOutputPolicy::Inherit => (Stdio::inherit(), Stdio::inherit()),
+
+            CommandOutputPolicy::Template(template) => match iteration.to_env_var_value() {
+                // The output of intermediate commands like '--prepare' is not captured
+                None => (Stdio::null(), Stdio::null()),
+                Some(iteration) => {
+                    let stdout = Self::create_template_file(template, &iteration, "stdout")?;
+                    let stderr = if template.contains("{stream}") {
+                        Self::create_template_file(template, &iteration, "stderr")?
+                    } else {
+                        // Without '{stream}', both streams are written to the same file
+                        stdout.try_clone()?
+                    };
+                    (stdout.into(), stderr.into())
+                }
+            },
         };
 
         Ok(streams)
@@ -194,6 +518,105 @@ impl Default for ExecutorKind {
     }
 }
 
+/// How to clear the page cache before each run of a benchmarked command (Linux only)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColdCache {
+    /// Drop the whole page cache (requires root privileges)
+    DropAll,
+
+    /// Only evict the given files from the page cache
+    EvictFiles(Vec<String>),
+}
+
+/// Checks of the output of the benchmarked commands, to make sure that they produce the
+/// correct results
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OutputCheck {
+    /// Expected digests of the standard output (once or once per command)
+    pub expected: Option<Vec<OutputDigest>>,
+
+    /// Whether all commands have to produce identical output
+    pub identical: bool,
+
+    /// Whether to check the output of every benchmark run (instead of only the first one).
+    /// Without an expected output, every run is compared with the first run.
+    pub all_runs: bool,
+
+    /// Whether to only warn (instead of failing) if the output does not match
+    pub warn_only: bool,
+}
+
+impl OutputCheck {
+    /// Whether any output check is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.expected.is_some() || self.identical || self.all_runs
+    }
+
+    /// The expected output digest for the benchmark with the given number
+    pub fn expected_for(&self, number: usize) -> Option<OutputDigest> {
+        self.expected.as_ref().map(|digests| {
+            if digests.len() == 1 {
+                digests[0]
+            } else {
+                digests[number]
+            }
+        })
+    }
+}
+
+/// The number of CPUs that can be selected via '--cpu-affinity'
+#[cfg(target_os = "linux")]
+const CPU_SETSIZE: usize = libc::CPU_SETSIZE as usize;
+#[cfg(not(target_os = "linux"))]
+const CPU_SETSIZE: usize = 1024;
+
+/// Scheduling settings for the benchmarked processes. These are applied in the child
+/// process before the command is executed (Linux only).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProcessScheduling {
+    /// CPUs that the benchmarked processes are pinned to
+    pub cpu_affinity: Option<Vec<usize>>,
+
+    /// Niceness of the benchmarked processes
+    pub nice: Option<i32>,
+
+    /// Real-time priority for the SCHED_FIFO scheduling policy
+    pub sched_fifo: Option<i32>,
+}
+
+impl ProcessScheduling {
+    /// Parse a list of CPUs like "2,3" or "0-3,6". The CPU numbers have to fit into a
+    /// `cpu_set_t`.
+    pub fn parse_cpu_list<'a>(list: &str) -> Result<Vec<usize>, OptionsError<'a>> {
+        let invalid = || OptionsError::InvalidCpuList(list.to_string());
+
+        let mut cpus = vec![];
+        for item in list.split(',').map(str::trim) {
+            match item.split_once('-') {
+                Some((first, last)) => {
+                    let first: usize = first.trim().parse().map_err(|_| invalid())?;
+                    let last: usize = last.trim().parse().map_err(|_| invalid())?;
+                    if first > last || last >= CPU_SETSIZE {
+                        return Err(invalid());
+                    }
+                    cpus.extend(first..=last);
+                }
+                None => {
+                    let cpu: usize = item.parse().map_err(|_| invalid())?;
+                    if cpu >= CPU_SETSIZE {
+                        return Err(invalid());
+                    }
+                    cpus.push(cpu);
+                }
+            }
+        }
+        cpus.sort_unstable();
+        cpus.dedup();
+
+        Ok(cpus)
+    }
+}
+
 /// The main settings for a hyperfine benchmark session
 pub struct Options {
     /// Upper and lower bound for the number of benchmark runs
@@ -229,12 +652,48 @@ pub struct Options {
     /// What color mode to use for the terminal output
     pub output_style: OutputStyleOption,
 
+    /// Whether or not to show a histogram and a sparkline of the run times
+    pub show_histogram: bool,
+
     /// How to order benchmarks in the relative speed comparison
     pub sort_order_speed_comparison: SortOrder,
 
     /// How to order benchmarks in the markup format exports
     pub sort_order_exports: SortOrder,
 
+    /// Which statistic to use for the relative speed comparison and for sorting
+    pub statistic: SummaryStatistic,
+
+    /// What to do with statistical outliers
+    pub outlier_policy: OutlierPolicy,
+
+    /// Whether to refuse benchmarking on a noisy system
+    pub strict_environment: bool,
+
+    /// CPU affinity and priority of the benchmarked processes
+    pub scheduling: ProcessScheduling,
+
+    /// Whether (and how) to clear the page cache before each run
+    pub cold_cache: Option<ColdCache>,
+
+    /// Additional environment variables for all benchmarked commands (from all '--env' options)
+    pub environment: Vec<String>,
+
+    /// Working directory for the benchmarked commands (once or once per command)
+    pub working_directory: Option<Vec<String>>,
+
+    /// Whether to measure the shell spawning time again before each benchmark
+    pub recalibrate: bool,
+
+    /// Whether to print the result of the shell spawning time calibration
+    pub show_calibration: bool,
+
+    /// Whether to include the times of warmup runs and intermediate commands in the JSON export
+    pub export_phase_times: bool,
+
+    /// Which checks to perform on the output of the benchmarked commands
+    pub output_check: OutputCheck,
+
     /// Determines how we run commands
     pub executor_kind: ExecutorKind,
 
@@ -246,6 +705,9 @@ pub struct Options {
 
     /// Which time unit to use when displaying results
     pub time_unit: Option<Unit>,
+
+    /// Which percentiles of the run times to compute
+    pub percentiles: Vec<Scalar>,
 }
 
 impl Default for Options {
@@ -262,12 +724,25 @@ impl Default for Options {
             setup_command: None,
             cleanup_command: None,
             output_style: OutputStyleOption::Full,
+            show_histogram: false,
             sort_order_speed_comparison: SortOrder::MeanTime,
             sort_order_exports: SortOrder::Command,
+            statistic: SummaryStatistic::Mean,
+            outlier_policy: OutlierPolicy::Keep,
+            strict_environment: false,
+            scheduling: ProcessScheduling::default(),
+            cold_cache: None,
+            environment: vec![],
+            working_directory: None,
+            recalibrate: false,
+            show_calibration: false,
+            export_phase_times: false,
+            output_check: OutputCheck::default(),
             executor_kind: ExecutorKind::default(),
             command_output_policies: vec![CommandOutputPolicy::Null],
             time_unit: None,
             command_input_policy: CommandInputPolicy::Null,
+            percentiles: vec![50.0, 90.0, 95.0, 99.0],
         }
     }
 }
@@ -331,6 +806,15 @@ impl Options {
 
         options.cleanup_command = matches.get_one::<String>("cleanup").map(String::from);
 
+        options.environment = matches
+            .get_many::<String>("env")
+            .map(|values| values.map(String::from).collect())
+            .unwrap_or_default();
+
+        options.working_directory = matches
+            .get_many::<String>("cwd")
+            .map(|values| values.map(String::from).collect::<Vec<String>>());
+
         options.command_output_policies = if matches.get_flag("show-output") {
             vec![CommandOutputPolicy::Inherit]
         } else if let Some(output_values) = matches.get_many::<String>("output") {
@@ -340,6 +824,12 @@ impl Options {
                     "null" => CommandOutputPolicy::Null,
                     "pipe" => CommandOutputPolicy::Pipe,
                     "inherit" => CommandOutputPolicy::Inherit,
+                    arg if CommandOutputPolicy::TEMPLATE_PLACEHOLDERS
+                        .iter()
+                        .any(|p| arg.contains(p)) =>
+                    {
+                        CommandOutputPolicy::Template(arg.to_string())
+                    }
                     arg => {
                         let path = PathBuf::from(arg);
                         if path.components().count() <= 1 {
@@ -351,11 +841,73 @@ impl Options {
                 policies.push(policy);
             }
             policies
+        } else if matches.contains_id("stdout") || matches.contains_id("stderr") {
+            let parse = |id: &str| -> Result<Vec<StreamPolicy>, OptionsError> {
+                match matches.get_many::<String>(id) {
+                    Some(values) => values.map(|v| StreamPolicy::from_str(v)).collect(),
+                    None => Ok(vec![StreamPolicy::Null]),
+                }
+            };
+            let stdout = parse("stdout")?;
+            let stderr = parse("stderr")?;
+            if stdout.len() > 1 && stderr.len() > 1 && stdout.len() != stderr.len() {
+                return Err(OptionsError::StreamPolicyCountMismatch);
+            }
+            let pick = |policies: &[StreamPolicy], i: usize| {
+                policies.get(i).unwrap_or(&policies[0]).clone()
+            };
+            (0..cmp::max(stdout.len(), stderr.len()))
+                .map(|i| CommandOutputPolicy::Streams {
+                    stdout: pick(&stdout, i),
+                    stderr: pick(&stderr, i),
+                })
+                .collect()
         } else {
             vec![CommandOutputPolicy::Null]
         };
 
+        options.output_check.expected =
+            if let Some(files) = matches.get_many::<String>("expect-output") {
+                let mut digests = vec![];
+                for file in files {
+                    let content = std::fs::read(file)
+                        .map_err(|_| OptionsError::ExpectedOutputFileNotReadable(file.clone()))?;
+                    digests.push(OutputDigest::of(&content));
+                }
+                Some(digests)
+            } else if let Some(hashes) = matches.get_many::<String>("expect-output-hash") {
+                let mut digests = vec![];
+                for hash in hashes {
+                    digests.push(
+                        OutputDigest::from_hex(hash)
+                            .ok_or_else(|| OptionsError::InvalidOutputHash(hash.clone()))?,
+                    );
+                }
+                Some(digests)
+            } else {
+                None
+            };
+        options.output_check.identical = matches.get_flag("expect-identical-output");
+        options.output_check.all_runs = matches
+            .get_one::<String>("check-output")
+            .map(|s| s.as_str())
+            == Some("all");
+        options.output_check.warn_only = matches
+            .get_one::<String>("output-mismatch")
+            .map(|s| s.as_str())
+            == Some("warn");
+
+        let events_on_stdout = matches
+            .get_one::<String>("export-ndjson")
+            .map(|target| target == "-")
+            .unwrap_or(false);
         options.output_style = match matches.get_one::<String>("style").map(|s| s.as_str()) {
+            Some(style) if events_on_stdout && style != "none" => {
+                return Err(OptionsError::NdjsonOnStdoutWithOutputStyle(
+                    style.to_string(),
+                ));
+            }
+            None if events_on_stdout => OutputStyleOption::Disabled,
             Some("full") => OutputStyleOption::Full,
             Some("basic") => OutputStyleOption::Basic,
             Some("nocolor") => OutputStyleOption::NoColor,
@@ -364,7 +916,8 @@ impl Options {
             _ => {
                 if options
                     .command_output_policies
-                    .contains(&CommandOutputPolicy::Inherit)
+                    .iter()
+                    .any(CommandOutputPolicy::shows_output)
                     || !io::stdout().is_terminal()
                 {
                     OutputStyleOption::Basic
@@ -392,6 +945,8 @@ impl Options {
             OutputStyleOption::Disabled => {}
         };
 
+        options.show_histogram = matches.get_flag("histogram");
+
         (
             options.sort_order_speed_comparison,
             options.sort_order_exports,
@@ -402,6 +957,79 @@ impl Options {
             Some(_) => unreachable!("Unknown sort order"),
         };
 
+        if let Some(statistic) = matches.get_one::<String>("statistic") {
+            options.statistic = SummaryStatistic::parse_from_str(statistic)?;
+        }
+
+        options.outlier_policy = match matches.get_one::<String>("outliers").map(|s| s.as_str()) {
+            None | Some("keep") => OutlierPolicy::Keep,
+            Some("remove") => OutlierPolicy::Remove,
+            Some("winsorize") => OutlierPolicy::Winsorize,
+            Some(_) => unreachable!("Unknown outlier policy"),
+        };
+
+        options.strict_environment = matches.get_flag("strict-environment");
+
+        let param_to_i32 = |param| {
+            matches
+                .get_one::<String>(param)
+                .map(|n| {
+                    n.parse::<i32>()
+                        .map_err(|e| OptionsError::IntParsingError(param, e))
+                })
+                .transpose()
+        };
+
+        options.scheduling.cpu_affinity = matches
+            .get_one::<String>("cpu-affinity")
+            .map(|list| ProcessScheduling::parse_cpu_list(list))
+            .transpose()?;
+        options.scheduling.nice = match param_to_i32("nice")? {
+            Some(nice) if !(-20..=19).contains(&nice) => {
+                return Err(OptionsError::InvalidNiceness(nice))
+            }
+            nice => nice,
+        };
+        options.scheduling.sched_fifo = match param_to_i32("sched-fifo")? {
+            Some(priority) if !(1..=99).contains(&priority) => {
+                return Err(OptionsError::InvalidRealtimePriority(priority))
+            }
+            priority => priority,
+        };
+
+        options.cold_cache = if matches.get_flag("cold-cache") {
+            Some(ColdCache::DropAll)
+        } else if let Some(files) = matches.get_many::<String>("cold-cache-file") {
+            let files: Vec<String> = files.cloned().collect();
+            if let Some(missing) = files.iter().find(|f| !Path::new(f).is_file()) {
+                return Err(OptionsError::ColdCacheFileDoesNotExist(missing.clone()));
+            }
+            Some(ColdCache::EvictFiles(files))
+        } else {
+            None
+        };
+
+        if cfg!(not(target_os = "linux")) {
+            for (param, used) in [
+                ("cpu-affinity", options.scheduling.cpu_affinity.is_some()),
+                ("nice", options.scheduling.nice.is_some()),
+                ("sched-fifo", options.scheduling.sched_fifo.is_some()),
+                ("cold-cache", options.cold_cache == Some(ColdCache::DropAll)),
+                (
+                    "cold-cache-file",
+                    matches!(options.cold_cache, Some(ColdCache::EvictFiles(_))),
+                ),
+            ] {
+                if used {
+                    return Err(OptionsError::LinuxOnlyOption(param));
+                }
+            }
+        }
+
+        options.recalibrate = matches.get_flag("recalibrate");
+        options.show_calibration = matches.get_flag("show-calibration");
+        options.export_phase_times = matches.get_flag("export-phase-times");
+
         options.executor_kind = if matches.get_flag("no-shell") {
             ExecutorKind::Raw
         } else {
@@ -448,18 +1076,19 @@ impl Options {
                 .map_err(|e| OptionsError::FloatParsingError("min-benchmarking-time", e))?;
         }
 
-        options.command_input_policy = if let Some(path_str) = matches.get_one::<String>("input") {
-            if path_str == "null" {
-                CommandInputPolicy::Null
-            } else {
-                let path = PathBuf::from(path_str);
-                if !path.exists() {
-                    return Err(OptionsError::StdinDataFileDoesNotExist(
-                        path_str.to_string(),
-                    ));
-                }
-                CommandInputPolicy::File(path)
+        if let Some(list) = matches.get_one::<String>("percentiles") {
+            options.percentiles = parse_percentiles(list)?;
+        }
+
+        // A percentile that is used as the summary statistic is always computed
+        if let SummaryStatistic::Percentile(p) = options.statistic {
+            if !options.percentiles.contains(&p) {
+                options.percentiles.push(p);
             }
+        }
+
+        options.command_input_policy = if let Some(value) = matches.get_one::<String>("input") {
+            CommandInputPolicy::from_str(value)?
         } else {
             CommandInputPolicy::Null
         };
@@ -487,19 +1116,100 @@ impl Options {
             );
         }
 
+        for env in &self.environment {
+            parse_environment(env)?;
+        }
+
+        if let Some(working_directory) = &self.working_directory {
+            ensure!(
+                working_directory.len() <= 1 || num_commands == working_directory.len(),
+                "The '--cwd' option has to be provided just once or N times, where N={num_commands} is the \
+                 number of benchmark commands (including a potential reference)."
+            );
+        }
+
+        if let Some(expected) = &self.output_check.expected {
+            ensure!(
+                expected.len() <= 1 || num_commands == expected.len(),
+                "The '--expect-output'/'--expect-output-hash' options have to be provided just once or N \
+                 times, where N={num_commands} is the number of benchmark commands (including a potential \
+                 reference)."
+            );
+        }
+
+        if self.output_check.is_enabled() {
+            ensure!(
+                self.command_output_policies
+                    .iter()
+                    .all(|p| p.for_output_check().is_some()),
+                "The output of the commands needs to be captured in order to check it. Options like \
+                 '--expect-output' can therefore not be combined with '--show-output', \
+                 '--output=inherit|<FILE>' or '--stdout=inherit|file:<FILE>'."
+            );
+        }
+
+        if self
+            .command_output_policies
+            .iter()
+            .any(|p| matches!(p, CommandOutputPolicy::Template(_)))
+        {
+            for (name, _) in commands.iter().flat_map(|cmd| cmd.get_parameters()) {
+                ensure!(
+                    !CommandOutputPolicy::TEMPLATE_PLACEHOLDERS.contains(&format!("{{{name}}}").as_str()),
+                    "The parameter name '{name}' can not be used in combination with an '--output' path \
+                     template, because '{{{name}}}' is a placeholder of the template. Please rename the \
+                     parameter."
+                );
+            }
+        }
+
         if self.command_output_policies.len() == 1 {
             self.command_output_policies =
                 vec![self.command_output_policies[0].clone(); num_commands];
         } else {
             ensure!(
                 self.command_output_policies.len() == num_commands,
-                "The '--output' option has to be provided just once or N times, where N={num_commands} is the \
-                 number of benchmark commands (including a potential reference)."
+                "The '--output' (or '--stdout'/'--stderr') option has to be provided just once or N times, \
+                 where N={num_commands} is the number of benchmark commands (including a potential \
+                 reference)."
             );
         }
 
         Ok(())
     }
+
+    /// The working directory for the benchmark with the given number
+    pub fn working_directory_for(&self, number: usize) -> Option<&str> {
+        value_for_command(&self.working_directory, number)
+    }
+}
+
+/// Select the value of an option that can be given once (for all commands) or once per command
+fn value_for_command(values: &Option<Vec<String>>, number: usize) -> Option<&str> {
+    values.as_ref().and_then(|values| {
+        if values.len() == 1 {
+            values.first()
+        } else {
+            values.get(number)
+        }
+        .map(String::as_str)
+    })
+}
+
+/// Parse a comma-separated list of percentiles like "50,90,p99.9"
+pub fn parse_percentiles<'a>(list: &str) -> Result<Vec<Scalar>, OptionsError<'a>> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            if s.starts_with('p') {
+                Percentiles::parse_name(s)
+            } else {
+                Percentiles::parse_name(&format!("p{s}"))
+            }
+            .ok_or_else(|| OptionsError::InvalidPercentile(s.to_string()))
+        })
+        .collect()
 }
 
 #[test]
@@ -545,3 +1255,186 @@ fn test_can_parse_shell_command_line_from_str() {
         OptionsError::EmptyShell
     ));
 }
+
+#[test]
+fn test_parse_percentiles() {
+    assert_eq!(
+        parse_percentiles("50,90,95,99").unwrap(),
+        vec![50.0, 90.0, 95.0, 99.0]
+    );
+    assert_eq!(parse_percentiles("p75, p99.9").unwrap(), vec![75.0, 99.9]);
+    assert_eq!(parse_percentiles("").unwrap(), Vec::<Scalar>::new());
+
+    assert!(matches!(
+        parse_percentiles("50,101").unwrap_err(),
+        OptionsError::InvalidPercentile(p) if p == "101"
+    ));
+    assert!(matches!(
+        parse_percentiles("median").unwrap_err(),
+        OptionsError::InvalidPercentile(_)
+    ));
+    assert!(matches!(
+        parse_percentiles("ppp50").unwrap_err(),
+        OptionsError::InvalidPercentile(p) if p == "ppp50"
+    ));
+}
+
+#[test]
+fn test_parse_summary_statistic() {
+    assert_eq!(
+        SummaryStatistic::parse_from_str("median").unwrap(),
+        SummaryStatistic::Median
+    );
+    assert_eq!(
+        SummaryStatistic::parse_from_str("p99.9").unwrap(),
+        SummaryStatistic::Percentile(99.9)
+    );
+    assert_eq!(
+        format!("{}", SummaryStatistic::Percentile(95.0)),
+        "p95".to_string()
+    );
+
+    assert!(matches!(
+        SummaryStatistic::parse_from_str("max").unwrap_err(),
+        OptionsError::UnknownStatistic(_)
+    ));
+    assert!(matches!(
+        SummaryStatistic::parse_from_str("p200").unwrap_err(),
+        OptionsError::UnknownStatistic(_)
+    ));
+}
+
+#[test]
+fn test_parse_cpu_list() {
+    assert_eq!(
+        ProcessScheduling::parse_cpu_list("2,3").unwrap(),
+        vec![2, 3]
+    );
+    assert_eq!(
+        ProcessScheduling::parse_cpu_list("6, 0-3,2").unwrap(),
+        vec![0, 1, 2, 3, 6]
+    );
+
+    assert_eq!(
+        ProcessScheduling::parse_cpu_list(&format!("{}", CPU_SETSIZE - 1)).unwrap(),
+        vec![CPU_SETSIZE - 1]
+    );
+
+    for invalid in [
+        "",
+        "a",
+        "1,",
+        "3-1",
+        "-1",
+        "1-",
+        "5000",
+        "0-5000",
+        "1,99999999999",
+    ] {
+        assert!(matches!(
+            ProcessScheduling::parse_cpu_list(invalid).unwrap_err(),
+            OptionsError::InvalidCpuList(_)
+        ));
+    }
+}
+
+#[test]
+fn test_input_policy_from_str() {
+    use crate::command::Command;
+    use crate::parameter::ParameterValue;
+
+    assert_eq!(
+        CommandInputPolicy::from_str("null").unwrap(),
+        CommandInputPolicy::Null
+    );
+    assert!(matches!(
+        CommandInputPolicy::from_str("pipe:does-not-exist.txt"),
+        Err(OptionsError::StdinDataFileDoesNotExist(_))
+    ));
+
+    let policy =
+        CommandInputPolicy::from_str("pipe:template:inputs/{size}/$HYPERFINE_ITERATION").unwrap();
+    let CommandInputPolicy::Pipe(source) = policy else {
+        panic!("Expected pipe input policy");
+    };
+
+    let command = Command::new_parametrized(
+        None,
+        "cat",
+        vec![("size", ParameterValue::Text("large".into()))],
+    );
+    assert_eq!(
+        source.path_for(&command, &BenchmarkIteration::Benchmark(3)),
+        Some(PathBuf::from("inputs/large/3"))
+    );
+    assert_eq!(
+        source.path_for(&command, &BenchmarkIteration::NonBenchmarkRun),
+        None
+    );
+
+    let directory = InputSource::Directory(vec!["a".into(), "b".into()]);
+    let paths: Vec<_> = [
+        BenchmarkIteration::Warmup(0),
+        BenchmarkIteration::Benchmark(0),
+        BenchmarkIteration::Benchmark(1),
+        BenchmarkIteration::Benchmark(2),
+    ]
+    .iter()
+    .map(|i| directory.path_for(&command, i).unwrap())
+    .collect();
+    assert_eq!(paths, ["a", "a", "b", "a"].map(PathBuf::from));
+}
+
+#[test]
+fn test_output_template_for_command() {
+    use crate::command::Command;
+    use crate::parameter::ParameterValue;
+
+    let policy = CommandOutputPolicy::Template(
+        "logs/{command_index}-{command_name}-{size}/{iteration}.{stream}".into(),
+    );
+    let command = Command::new_parametrized(
+        None,
+        "sort -n {size}.txt",
+        vec![("size", ParameterValue::Text("large".into()))],
+    );
+    assert_eq!(
+        policy.for_command(1, &command),
+        CommandOutputPolicy::Template("logs/2-sort_-n_large.txt-large/{iteration}.{stream}".into())
+    );
+
+    assert_eq!(
+        CommandOutputPolicy::Pipe.for_command(1, &command),
+        CommandOutputPolicy::Pipe
+    );
+}
+
+#[test]
+fn test_stream_policy_from_str() {
+    assert_eq!(StreamPolicy::from_str("null").unwrap(), StreamPolicy::Null);
+    assert_eq!(StreamPolicy::from_str("pipe").unwrap(), StreamPolicy::Pipe);
+    assert_eq!(
+        StreamPolicy::from_str("inherit").unwrap(),
+        StreamPolicy::Inherit
+    );
+    assert_eq!(
+        StreamPolicy::from_str("file:err.log").unwrap(),
+        StreamPolicy::File(PathBuf::from("err.log"))
+    );
+    assert!(StreamPolicy::from_str("file:").is_err());
+    assert!(StreamPolicy::from_str("err.log").is_err());
+
+    let policy = CommandOutputPolicy::Streams {
+        stdout: StreamPolicy::Pipe,
+        stderr: StreamPolicy::Inherit,
+    };
+    assert!(policy.shows_output());
+    assert_eq!(
+        policy.for_output_check(),
+        Some(CommandOutputPolicy::Streams {
+            stdout: StreamPolicy::Digest,
+            stderr: StreamPolicy::Inherit
+        })
+    );
+    assert_eq!(CommandOutputPolicy::Inherit.for_output_check(), None);
+}
@@ -24,11 +24,8 @@ pub fn modified_zscores(xs: &[f64]) -> Vec<f64> {
     // Compute sample median:
     let x_median = median(xs);
 
-    // Compute the absolute deviations from the median:
-    let deviations: Vec<f64> = xs.iter().map(|x| (x - x_median).abs()).collect();
-
     // Compute median absolute deviation:
-    let mad = median(&deviations);
+    let mad = median_absolute_deviation(xs);
 
     // Handle MAD == 0 case
     let mad = if mad > 0.0 { mad } else { f64::EPSILON };
@@ -37,19 +34,54 @@ pub fn modified_zscores(xs: &[f64]) -> Vec<f64> {
     xs.iter().map(|&x| (x - x_median) / mad).collect()
 }
 
-/// Return the number of outliers in a given sample. Outliers are defined as data points with a
-/// modified Z-score that is larger than `OUTLIER_THRESHOLD`.
-#[cfg(test)]
-pub fn num_outliers(xs: &[f64]) -> usize {
+/// Compute the median absolute deviation (MAD) of a given (non-empty) sample.
+pub fn median_absolute_deviation(xs: &[f64]) -> f64 {
+    let x_median = median(xs);
+    let deviations: Vec<f64> = xs.iter().map(|x| (x - x_median).abs()).collect();
+    median(&deviations)
+}
+
+/// Return the indices of all outliers in a given sample. Outliers are defined as data points
+/// with a modified Z-score that is larger than `OUTLIER_THRESHOLD`.
+pub fn outlier_indices(xs: &[f64]) -> Vec<usize> {
     if xs.is_empty() {
-        return 0;
+        return vec![];
     }
 
-    let scores = modified_zscores(xs);
-    scores
+    modified_zscores(xs)
         .iter()
-        .filter(|&&s| s.abs() > OUTLIER_THRESHOLD)
-        .count()
+        .enumerate()
+        .filter(|(_, &s)| s.abs() > OUTLIER_THRESHOLD)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Return the given sample without the data points at the given indices.
+pub fn remove_outliers(xs: &[f64], outliers: &[usize]) -> Vec<f64> {
+    xs.iter()
+        .enumerate()
+        .filter(|(i, _)| !outliers.contains(i))
+        .map(|(_, &x)| x)
+        .collect()
+}
+
+/// Clamp the data points at the given indices to the range of the remaining data points.
+pub fn winsorize(xs: &[f64], outliers: &[usize]) -> Vec<f64> {
+    let inliers = remove_outliers(xs, outliers);
+    if inliers.is_empty() {
+        return xs.to_vec();
+    }
+
+    let lower = inliers.iter().copied().fold(f64::INFINITY, f64::min);
+    let upper = inliers.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    xs.iter().map(|&x| x.clamp(lower, upper)).collect()
+}
+
+/// Return the number of outliers in a given sample.
+#[cfg(test)]
+pub fn num_outliers(xs: &[f64]) -> usize {
+    outlier_indices(xs).len()
 }
 
 #[test]
@@ -113,3 +145,22 @@ fn test_detect_outliers_if_mad_becomes_0() {
     let xs = [10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 100.0, 100.0];
     assert_eq!(2, num_outliers(&xs));
 }
+
+#[test]
+fn test_remove_and_winsorize_outliers() {
+    let xs = [0.30, 0.5, 0.29, 0.31, 0.30, 0.01];
+    let outliers = outlier_indices(&xs);
+    assert_eq!(outliers, vec![1, 5]);
+
+    assert_eq!(
+        remove_outliers(&xs, &outliers),
+        vec![0.30, 0.29, 0.31, 0.30]
+    );
+    assert_eq!(
+        winsorize(&xs, &outliers),
+        vec![0.30, 0.31, 0.29, 0.31, 0.30, 0.29]
+    );
+
+    assert_eq!(remove_outliers(&xs, &[]), xs.to_vec());
+    assert_eq!(winsorize(&xs, &[]), xs.to_vec());
+}
@@ -0,0 +1,182 @@
+use std::env;
+
+use colored::*;
+use console::Term;
+
+use crate::options::OutputStyleOption;
+use crate::output::format::format_duration;
+use crate::util::min_max::{max, min};
+use crate::util::units::{Second, Unit};
+
+/// Maximum width (in characters) of the terminal histogram and sparkline
+const MAX_WIDTH: usize = 50;
+
+/// Number of terminal lines used for the histogram bars
+const HISTOGRAM_HEIGHT: usize = 3;
+
+/// Block characters with increasing height (in steps of 1/8)
+const BLOCKS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// ASCII characters with increasing density, used instead of `BLOCKS` for '--style=basic'
+/// and '--style=nocolor'
+const ASCII_BLOCKS: [char; 9] = [' ', '.', ':', '-', '=', '+', '*', '%', '#'];
+
+/// Number of histogram bins for a sample of the given size (square-root rule)
+pub fn bin_count(num_values: usize, max_bins: usize) -> usize {
+    ((num_values as f64).sqrt().ceil() as usize).clamp(1, max_bins.max(1))
+}
+
+/// Sort the given times into `num_bins` equally sized bins between the minimum and the maximum.
+pub fn bin_counts(times: &[Second], num_bins: usize) -> Vec<usize> {
+    let mut counts = vec![0; num_bins.max(1)];
+    if times.is_empty() {
+        return counts;
+    }
+
+    let t_min = min(times);
+    let range = max(times) - t_min;
+
+    for &t in times {
+        let bin = if range > 0.0 {
+            (((t - t_min) / range) * counts.len() as f64) as usize
+        } else {
+            0
+        };
+        let last = counts.len() - 1;
+        counts[bin.min(last)] += 1;
+    }
+    counts
+}
+
+/// Available width for the plots. This is based on the size of the terminal, or on
+/// the `COLUMNS` environment variable if the output is not a terminal.
+fn available_width(indent: usize) -> usize {
+    Term::stdout()
+        .size_checked()
+        .map(|(_rows, columns)| columns as usize)
+        .or_else(|| env::var("COLUMNS").ok().and_then(|c| c.parse().ok()))
+        .map(|columns| columns.saturating_sub(indent + 24).clamp(10, MAX_WIDTH))
+        .unwrap_or(MAX_WIDTH)
+}
+
+/// Render a histogram of the given times with `height` lines of block characters.
+/// The returned lines are ordered from top to bottom.
+fn render_histogram(
+    times: &[Second],
+    width: usize,
+    height: usize,
+    blocks: &[char; 9],
+) -> Vec<String> {
+    let counts = bin_counts(times, bin_count(times.len(), width));
+    let max_count = counts.iter().copied().max().unwrap_or(0).max(1);
+    let steps = height * (blocks.len() - 1);
+
+    (0..height)
+        .rev()
+        .map(|row| {
+            counts
+                .iter()
+                .map(|&count| {
+                    let level = (count * steps).div_ceil(max_count);
+                    let in_row = level.saturating_sub(row * (blocks.len() - 1));
+                    blocks[in_row.min(blocks.len() - 1)]
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Render the times in the order they have been measured as a single line of block
+/// characters. If there are more values than available characters, consecutive values
+/// are averaged.
+fn render_sparkline(times: &[Second], width: usize, blocks: &[char; 9]) -> String {
+    if times.is_empty() {
+        return String::new();
+    }
+
+    let chunk_size = times.len().div_ceil(width.max(1));
+    let values: Vec<Second> = times
+        .chunks(chunk_size)
+        .map(|chunk| chunk.iter().sum::<Second>() / chunk.len() as f64)
+        .collect();
+
+    let v_min = min(&values);
+    let range = max(&values) - v_min;
+
+    values
+        .iter()
+        .map(|&v| {
+            let level = if range > 0.0 {
+                1 + (((v - v_min) / range) * (blocks.len() - 2) as f64).round() as usize
+            } else {
+                blocks.len() / 2
+            };
+            blocks[level.min(blocks.len() - 1)]
+        })
+        .collect()
+}
+
+/// Print a histogram and a run-order sparkline of the given times to the terminal.
+/// Coloring follows the global setting that is derived from the `--style` option.
+pub fn print_distribution(times: &[Second], unit: Unit, style: OutputStyleOption) {
+    if times.len() < 2 {
+        return;
+    }
+
+    const INDENT: usize = 2;
+    let width = available_width(INDENT);
+    let blocks = match style {
+        OutputStyleOption::Basic | OutputStyleOption::NoColor => &ASCII_BLOCKS,
+        _ => &BLOCKS,
+    };
+
+    let lines = render_histogram(times, width, HISTOGRAM_HEIGHT, blocks);
+    let last = lines.len() - 1;
+    for (i, line) in lines.iter().enumerate() {
+        let label = if i == 0 { "Histogram:" } else { "" };
+        if i == last {
+            println!(
+                "{:INDENT$}{:<12}{}  {} … {}",
+                "",
+                label,
+                line.green(),
+                format_duration(min(times), Some(unit)).cyan(),
+                format_duration(max(times), Some(unit)).purple(),
+            );
+        } else {
+            println!("{:INDENT$}{:<12}{}", "", label, line.green());
+        }
+    }
+
+    println!(
+        "{:INDENT$}{:<12}{}",
+        "",
+        "Run order:",
+        render_sparkline(times, width, blocks).blue()
+    );
+}
+
+#[test]
+fn test_bin_counts() {
+    assert_eq!(bin_counts(&[1.0], 1), vec![1]);
+    assert_eq!(bin_counts(&[2.0, 2.0, 2.0, 2.0], 2), vec![4, 0]);
+    assert_eq!(bin_counts(&[1.0, 2.0, 3.0, 4.0], 2), vec![2, 2]);
+    assert_eq!(bin_counts(&[1.0, 1.1, 1.2, 5.0], 4), vec![3, 0, 0, 1]);
+}
+
+#[test]
+fn test_render_histogram() {
+    let lines = render_histogram(&[1.0, 1.1, 1.2, 5.0], 10, 2, &BLOCKS);
+    assert_eq!(lines, vec!["█ ", "█▆"]);
+
+    let lines = render_histogram(&[1.0, 1.1, 1.2, 5.0], 10, 2, &ASCII_BLOCKS);
+    assert_eq!(lines, vec!["# ", "#*"]);
+}
+
+#[test]
+fn test_render_sparkline() {
+    assert_eq!(render_sparkline(&[1.0, 2.0, 3.0], 10, &BLOCKS), "▁▅█");
+    assert_eq!(render_sparkline(&[1.0, 1.0], 10, &BLOCKS), "▄▄");
+    assert_eq!(render_sparkline(&[1.0, 3.0, 2.0, 4.0], 2, &BLOCKS), "▁█");
+    assert_eq!(render_sparkline(&[1.0, 2.0, 3.0], 10, &ASCII_BLOCKS), ".+#");
+}
@@ -1,3 +1,4 @@
 pub mod format;
+pub mod histogram;
 pub mod progress_bar;
 pub mod warnings;
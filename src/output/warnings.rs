@@ -1,5 +1,6 @@
 use std::fmt;
 
+use crate::benchmark::calibration::Calibration;
 use crate::benchmark::MIN_EXECUTION_TIME;
 use crate::output::format::format_duration;
 use crate::util::units::Second;
@@ -15,6 +16,13 @@ pub enum Warnings {
     NonZeroExitCode,
     SlowInitialRun(Second, OutlierWarningOptions),
     OutliersDetected(OutlierWarningOptions),
+    CpuGovernor(String),
+    TurboBoostEnabled(&'static str, &'static str),
+    AslrEnabled,
+    HighSystemLoad(f64),
+    SwapActivity(u64, Second),
+    OutputMismatch(String),
+    NegativeRunTime(Calibration),
 }
 
 impl fmt::Display for Warnings {
@@ -64,6 +72,46 @@ impl fmt::Display for Warnings {
                     " It might help to use the '--warmup' or '--prepare' options."
                 }
             ),
+            Warnings::CpuGovernor(ref governors) => write!(
+                f,
+                "The CPU frequency scaling governor is set to '{governors}', which keeps the CPU \
+                 at its lowest clock frequency. Consider switching to the 'performance' or \
+                 'schedutil' governor, e.g. via \
+                 'sudo cpupower frequency-set --governor performance'."
+            ),
+            Warnings::TurboBoostEnabled(path, value) => write!(
+                f,
+                "Turbo boost is enabled. The clock frequency depends on the temperature and on the \
+                 load of the other cores, which makes the results less stable. Consider disabling \
+                 it via 'echo {value} | sudo tee {path}'."
+            ),
+            Warnings::AslrEnabled => write!(
+                f,
+                "Address space layout randomization (ASLR) is enabled, which can cause the run time \
+                 to vary between runs. Consider disabling it via \
+                 'echo 0 | sudo tee /proc/sys/kernel/randomize_va_space'."
+            ),
+            Warnings::HighSystemLoad(load) => write!(
+                f,
+                "The system load average is {load:.2}. Other processes compete with the \
+                 benchmarked commands for CPU time. Consider re-running this benchmark on a quiet \
+                 system."
+            ),
+            Warnings::SwapActivity(pages, duration) => write!(
+                f,
+                "The system is swapping ({pages} pages were swapped in or out within {}). \
+                 Consider closing memory-intensive programs before running the benchmark.",
+                format_duration(duration, None)
+            ),
+            Warnings::OutputMismatch(ref message) => write!(f, "{message}"),
+            Warnings::NegativeRunTime(ref calibration) => write!(
+                f,
+                "Some run times are negative after subtracting the shell spawning time ({} ± {}). \
+                 The command is too fast to be measured reliably through a shell. Consider using \
+                 the `-N`/`--shell=none` option.",
+                format_duration(calibration.time_real, None),
+                format_duration(calibration.uncertainty, None),
+            ),
         }
     }
 }
@@ -8,7 +8,6 @@ mod unix_timer;
 
 #[cfg(target_os = "linux")]
 use nix::fcntl::{splice, SpliceFFlags};
-#[cfg(target_os = "linux")]
 use std::fs::File;
 #[cfg(target_os = "linux")]
 use std::os::fd::AsFd;
@@ -16,11 +15,12 @@ use std::os::fd::AsFd;
 #[cfg(target_os = "windows")]
 use windows_sys::Win32::System::Threading::CREATE_SUSPENDED;
 
+use crate::benchmark::output_check::OutputDigest;
 use crate::util::units::Second;
 use wall_clock_timer::WallClockTimer;
 
 use std::io::Read;
-use std::process::{ChildStdout, Command, ExitStatus};
+use std::process::{Command, ExitStatus};
 
 use anyhow::Result;
 
@@ -46,10 +46,26 @@ pub struct TimerResult {
     pub memory_usage_byte: u64,
     /// The exit status of the process
     pub status: ExitStatus,
+    /// Digest of the standard output, if it has been requested
+    pub stdout_digest: Option<OutputDigest>,
 }
 
+/// An output stream (stdout or stderr) of a child process
+#[cfg(target_os = "linux")]
+trait OutputStream: Read + AsFd {}
+
+#[cfg(target_os = "linux")]
+impl<T: Read + AsFd> OutputStream for T {}
+
+/// An output stream (stdout or stderr) of a child process
+#[cfg(not(target_os = "linux"))]
+trait OutputStream: Read {}
+
+#[cfg(not(target_os = "linux"))]
+impl<T: Read> OutputStream for T {}
+
 /// Discard the output of a child process.
-fn discard(output: ChildStdout) {
+fn discard(output: impl OutputStream) {
     const CHUNK_SIZE: usize = 64 << 10;
 
     #[cfg(target_os = "linux")]
@@ -79,8 +95,14 @@ fn discard(output: ChildStdout) {
     }
 }
 
-/// Execute the given command and return a timing summary
-pub fn execute_and_measure(mut command: Command) -> Result<TimerResult> {
+/// Execute the given command and return a timing summary. If `stdin_data` is given, its
+/// content is streamed to the standard input of the process (which needs to be piped).
+/// With `digest_output`, the (piped) standard output is hashed instead of being discarded.
+pub fn execute_and_measure(
+    mut command: Command,
+    stdin_data: Option<File>,
+    digest_output: bool,
+) -> Result<TimerResult> {
     #[cfg(not(windows))]
     let cpu_timer = self::unix_timer::CPUTimer::start();
 
@@ -101,9 +123,33 @@ pub fn execute_and_measure(mut command: Command) -> Result<TimerResult> {
         unsafe { self::windows_timer::CPUTimer::start_suspended_process(&child) }
     };
 
+    // Handle CommandInputPolicy::Pipe. The data is written from a separate thread such that
+    // we can read the output of the process at the same time.
+    let writer = stdin_data
+        .zip(child.stdin.take())
+        .map(|(mut data, mut stdin)| {
+            std::thread::spawn(move || {
+                // The process might exit without consuming its complete input
+                let _ = std::io::copy(&mut data, &mut stdin);
+            })
+        });
+
+    // Piped stderr is drained from a separate thread, such that the process does not block
+    // on a full pipe while we are reading stdout
+    let stderr_drain = child
+        .stderr
+        .take()
+        .map(|output| std::thread::spawn(move || discard(output)));
+
+    let mut stdout_digest = None;
     if let Some(output) = child.stdout.take() {
-        // Handle CommandOutputPolicy::Pipe
-        discard(output);
+        if digest_output {
+            // Handle StreamPolicy::Digest
+            stdout_digest = Some(OutputDigest::of_reader(output)?);
+        } else {
+            // Handle CommandOutputPolicy::Pipe
+            discard(output);
+        }
     }
 
     let status = child.wait()?;
@@ -111,11 +157,19 @@ pub fn execute_and_measure(mut command: Command) -> Result<TimerResult> {
     let time_real = wallclock_timer.stop();
     let (time_user, time_system, memory_usage_byte) = cpu_timer.stop();
 
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    if let Some(stderr_drain) = stderr_drain {
+        let _ = stderr_drain.join();
+    }
+
     Ok(TimerResult {
         time_real,
         time_user,
         time_system,
         memory_usage_byte,
         status,
+        stdout_digest,
     })
 }
@@ -1,5 +1,8 @@
 pub mod exit_code;
 pub mod min_max;
 pub mod number;
+pub mod page_cache;
+pub mod percentile;
 pub mod randomized_environment_offset;
+pub mod system_info;
 pub mod units;
@@ -0,0 +1,71 @@
+//! Native replacements for `sync; echo 3 > /proc/sys/vm/drop_caches` to benchmark
+//! commands with a cold file system cache (Linux only).
+
+use anyhow::Result;
+
+#[cfg(target_os = "linux")]
+use anyhow::{bail, Context};
+#[cfg(target_os = "linux")]
+use std::{fs, io::ErrorKind, os::unix::io::AsRawFd};
+
+/// Write all dirty pages to disk and drop the page cache, dentries and inodes.
+/// This requires root privileges.
+pub fn drop_page_cache() -> Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        const DROP_CACHES: &str = "/proc/sys/vm/drop_caches";
+
+        unsafe { libc::sync() };
+
+        match fs::write(DROP_CACHES, "3") {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => bail!(
+                "Permission denied while writing to '{DROP_CACHES}'. Dropping the page cache with \
+                 '--cold-cache' requires root privileges. Alternatively, use \
+                 '--cold-cache-file <FILE>' to evict specific files from the cache, which does \
+                 not require any special privileges."
+            ),
+            Err(e) => Err(e).with_context(|| format!("Failed to write to '{DROP_CACHES}'")),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        unreachable!("'--cold-cache' is rejected during option parsing")
+    }
+}
+
+/// Evict the pages of the given file from the page cache. Dirty pages are written to disk
+/// first, because they can not be evicted otherwise.
+pub fn evict_file(path: &str) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        let file = fs::File::open(path)
+            .with_context(|| format!("Failed to open '{path}' to evict it from the page cache"))?;
+        file.sync_data()
+            .with_context(|| format!("Failed to write dirty pages of '{path}' to disk"))?;
+
+        let ret = unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+        if ret != 0 {
+            return Err(std::io::Error::from_raw_os_error(ret))
+                .with_context(|| format!("Failed to evict '{path}' from the page cache"));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = path;
+        unreachable!("'--cold-cache-file' is rejected during option parsing")
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_evict_file() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    fs::write(file.path(), "hyperfine").unwrap();
+
+    evict_file(file.path().to_str().unwrap()).unwrap();
+    assert!(evict_file("/non/existent/file").is_err());
+}
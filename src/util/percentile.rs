@@ -0,0 +1,35 @@
+use crate::util::units::Scalar;
+
+/// Compute the `p`-th percentile (with `0 <= p <= 100`) of the given sample. Values in
+/// between two data points are linearly interpolated (this corresponds to the default
+/// method in NumPy and R).
+pub fn percentile(xs: &[Scalar], p: Scalar) -> Scalar {
+    assert!(!xs.is_empty());
+    assert!((0.0..=100.0).contains(&p));
+
+    let mut sorted = xs.to_vec();
+    sorted.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let rank = p / 100.0 * (sorted.len() - 1) as Scalar;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let fraction = rank - lower as Scalar;
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * fraction
+}
+
+#[test]
+fn test_percentile() {
+    use approx::assert_relative_eq;
+
+    assert_relative_eq!(3.0, percentile(&[3.0], 0.0));
+    assert_relative_eq!(3.0, percentile(&[3.0], 99.0));
+
+    let xs = [5.0, 1.0, 4.0, 2.0, 3.0];
+    assert_relative_eq!(1.0, percentile(&xs, 0.0));
+    assert_relative_eq!(3.0, percentile(&xs, 50.0));
+    assert_relative_eq!(4.6, percentile(&xs, 90.0));
+    assert_relative_eq!(5.0, percentile(&xs, 100.0));
+
+    assert_relative_eq!(2.5, percentile(&[1.0, 2.0, 3.0, 4.0], 50.0));
+}
@@ -0,0 +1,254 @@
+//! Best-effort queries for information about the system that hyperfine is running on.
+//! All functions return `None` if the information is not available on the current platform.
+
+#[cfg(target_os = "linux")]
+use std::fs;
+
+/// Read a file from procfs/sysfs and return its trimmed content
+#[cfg(target_os = "linux")]
+fn read_trimmed(path: &str) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Find the value of a `key: value` line in a procfs file like `/proc/cpuinfo`
+#[cfg(target_os = "linux")]
+fn proc_value(path: &str, key: &str) -> Option<String> {
+    fs::read_to_string(path).ok()?.lines().find_map(|line| {
+        let (k, v) = line.split_once(':')?;
+        (k.trim() == key).then(|| v.trim().to_string())
+    })
+}
+
+/// Name of the host
+pub fn hostname() -> Option<String> {
+    #[cfg(unix)]
+    {
+        let mut buffer = [0u8; 256];
+        let ret = unsafe { libc::gethostname(buffer.as_mut_ptr() as *mut libc::c_char, 256) };
+        if ret != 0 {
+            return None;
+        }
+        let len = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+        Some(String::from_utf8_lossy(&buffer[..len]).into_owned())
+    }
+
+    #[cfg(windows)]
+    {
+        std::env::var("COMPUTERNAME").ok()
+    }
+}
+
+/// Human readable name of the operating system (distribution), e.g. "Ubuntu 24.04.1 LTS"
+pub fn os_name() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        fs::read_to_string("/etc/os-release")
+            .ok()?
+            .lines()
+            .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+            .map(|name| name.trim_matches('"').to_string())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Release of the operating system kernel, e.g. "6.8.0-45-generic"
+pub fn kernel_release() -> Option<String> {
+    #[cfg(unix)]
+    {
+        let mut uts: libc::utsname = unsafe { std::mem::zeroed() };
+        if unsafe { libc::uname(&mut uts) } != 0 {
+            return None;
+        }
+        let release = unsafe { std::ffi::CStr::from_ptr(uts.release.as_ptr()) };
+        Some(release.to_string_lossy().into_owned())
+    }
+
+    #[cfg(windows)]
+    {
+        None
+    }
+}
+
+/// Model name of the CPU
+pub fn cpu_model() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        proc_value("/proc/cpuinfo", "model name")
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Number of logical CPU cores that are available to hyperfine
+pub fn cpu_cores() -> Option<usize> {
+    std::thread::available_parallelism().ok().map(|n| n.get())
+}
+
+/// Total physical memory in bytes
+pub fn total_memory() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let value = proc_value("/proc/meminfo", "MemTotal")?;
+        let kibibytes: u64 = value.trim_end_matches("kB").trim().parse().ok()?;
+        Some(kibibytes * 1024)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Name of the CPU frequency scaling driver, e.g. "intel_pstate" or "acpi-cpufreq"
+pub fn cpu_frequency_driver() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        read_trimmed("/sys/devices/system/cpu/cpu0/cpufreq/scaling_driver")
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Frequency scaling governor of the given CPU, e.g. "performance" or "powersave"
+pub fn cpu_governor(cpu: usize) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        read_trimmed(&format!(
+            "/sys/devices/system/cpu/cpu{cpu}/cpufreq/scaling_governor"
+        ))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = cpu;
+        None
+    }
+}
+
+/// Frequency scaling governors of all CPUs (one entry per CPU that supports frequency scaling)
+pub fn cpu_governors() -> Vec<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") else {
+            return vec![];
+        };
+        let mut cpus: Vec<usize> = entries
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name();
+                name.to_str()?.strip_prefix("cpu")?.parse().ok()
+            })
+            .collect();
+        cpus.sort_unstable();
+        cpus.into_iter().filter_map(cpu_governor).collect()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        vec![]
+    }
+}
+
+/// Whether turbo boost (or a similar dynamic overclocking feature) is enabled. Returns the
+/// sysfs file which controls it, along with the value that disables it.
+pub fn turbo_boost() -> Option<(&'static str, &'static str)> {
+    #[cfg(target_os = "linux")]
+    {
+        const CONTROLS: [(&str, &str, &str); 2] = [
+            // (file, value if enabled, value to disable)
+            ("/sys/devices/system/cpu/intel_pstate/no_turbo", "0", "1"),
+            ("/sys/devices/system/cpu/cpufreq/boost", "1", "0"),
+        ];
+        CONTROLS.iter().find_map(|&(path, enabled, disable)| {
+            (read_trimmed(path)?.as_str() == enabled).then_some((path, disable))
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Address space layout randomization setting (0: disabled, 1: partial, 2: full)
+pub fn aslr_setting() -> Option<u8> {
+    #[cfg(target_os = "linux")]
+    {
+        read_trimmed("/proc/sys/kernel/randomize_va_space")?
+            .parse()
+            .ok()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Total number of pages that have been swapped in and out since boot
+pub fn swapped_pages() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let vmstat = fs::read_to_string("/proc/vmstat").ok()?;
+        let counter = |name: &str| {
+            vmstat.lines().find_map(|line| {
+                let (key, value) = line.split_once(' ')?;
+                if key == name {
+                    value.trim().parse::<u64>().ok()
+                } else {
+                    None
+                }
+            })
+        };
+        Some(counter("pswpin")? + counter("pswpout")?)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// System load averages over the last 1, 5 and 15 minutes
+pub fn load_average() -> Option<[f64; 3]> {
+    #[cfg(unix)]
+    {
+        let mut loadavg = [0.0f64; 3];
+        if unsafe { libc::getloadavg(loadavg.as_mut_ptr(), 3) } != 3 {
+            return None;
+        }
+        Some(loadavg)
+    }
+
+    #[cfg(windows)]
+    {
+        None
+    }
+}
+
+#[test]
+fn test_system_info() {
+    assert!(cpu_cores().unwrap_or(1) >= 1);
+
+    if let Some(loadavg) = load_average() {
+        assert!(loadavg.iter().all(|&l| l >= 0.0));
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        assert!(kernel_release().is_some());
+        assert!(total_memory().unwrap() > 0);
+    }
+}
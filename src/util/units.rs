@@ -31,6 +31,15 @@ impl Unit {
             Unit::MicroSecond => format!("{:.1}", value * 1e6),
         }
     }
+
+    /// Converts the Second value to the Unit.
+    pub fn convert(self, value: Second) -> Scalar {
+        match self {
+            Unit::Second => value,
+            Unit::MilliSecond => value * 1e3,
+            Unit::MicroSecond => value * 1e6,
+        }
+    }
 }
 
 #[test]
@@ -49,3 +58,10 @@ fn test_unit_format() {
 
     assert_eq!("1234.6", Unit::MicroSecond.format(0.00123456));
 }
+
+#[test]
+fn test_unit_convert() {
+    assert_eq!(1.5, Unit::Second.convert(1.5));
+    assert_eq!(1500.0, Unit::MilliSecond.convert(1.5));
+    assert_eq!(500000.0, Unit::MicroSecond.convert(0.5));
+}
@@ -561,3 +561,27 @@ fn setup_separate_prepare_reference_separate_conclude_cleanup_combined() {
         .expect_output("cleanup")
         .run();
 }
+
+#[test]
+fn output_check_run_is_executed_before_benchmarking_runs() {
+    ExecutionOrderTest::new()
+        .arg("--runs=2")
+        .arg("--warmup=1")
+        .arg("--expect-identical-output")
+        .prepare("prepare")
+        .command("command 1")
+        .conclude("conclude")
+        .expect_output("prepare")
+        .expect_output("command 1")
+        .expect_output("conclude")
+        .expect_output("prepare")
+        .expect_output("command 1")
+        .expect_output("conclude")
+        .expect_output("prepare")
+        .expect_output("command 1")
+        .expect_output("conclude")
+        .expect_output("prepare")
+        .expect_output("command 1")
+        .expect_output("conclude")
+        .run();
+}
@@ -390,51 +390,892 @@ fn can_pass_input_to_command_from_a_file() {
         .stdout(predicate::str::contains("This text is part of a file"));
 }
 
+#[test]
+fn can_stream_input_to_command_through_a_pipe() {
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--input=pipe:example_input_file.txt")
+        .arg("--show-output")
+        .arg(STDIN_READ_COMMAND)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("This text is part of a file"));
+}
+
+#[test]
+fn can_use_different_inputs_for_each_iteration() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    for i in 0..3 {
+        std::fs::write(
+            tempdir.path().join(format!("{i}.txt")),
+            format!("input-{i}\n"),
+        )
+        .unwrap();
+    }
+
+    let directory = tempdir.path().to_str().unwrap();
+    let template = format!("pipe:template:{directory}/$HYPERFINE_ITERATION.txt");
+    for input in [directory, template.as_str()] {
+        hyperfine()
+            .arg("--runs=3")
+            .arg("--input")
+            .arg(input)
+            .arg("--show-output")
+            .arg(STDIN_READ_COMMAND)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("input-0"))
+            .stdout(predicate::str::contains("input-1"))
+            .stdout(predicate::str::contains("input-2"));
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn reads_input_file_with_braces_in_the_path() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let input_path = tempdir.path().join("input-{n}.txt");
+    let prepared_path = tempdir.path().join("prepared.txt");
+    std::fs::write(&input_path, "file-content\n").unwrap();
+
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--input")
+        .arg(&input_path)
+        .arg("--prepare")
+        .arg(format!("cat > {}", prepared_path.to_str().unwrap()))
+        .arg("--show-output")
+        .arg(STDIN_READ_COMMAND)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("file-content"));
+
+    assert_eq!(
+        std::fs::read_to_string(prepared_path).unwrap(),
+        "file-content\n"
+    );
+}
+
+#[test]
+fn fails_for_missing_templated_input_file() {
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--input=template:missing-{n}.txt")
+        .arg("--parameter-list")
+        .arg("n")
+        .arg("7")
+        .arg(STDIN_READ_COMMAND)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Failed to open input file 'missing-7.txt'",
+        ));
+}
+
 #[test]
 fn fails_if_invalid_stdin_data_file_provided() {
     hyperfine()
-        .arg("--runs=1")
-        .arg("--input=example_non_existent_file.txt")
-        .arg("--show-output")
-        .arg(STDIN_READ_COMMAND)
+        .arg("--runs=1")
+        .arg("--input=example_non_existent_file.txt")
+        .arg("--show-output")
+        .arg(STDIN_READ_COMMAND)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The file 'example_non_existent_file.txt' specified as '--input' does not exist",
+        ));
+}
+
+#[cfg(unix)]
+#[test]
+fn redirects_stdout_and_stderr_independently() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let stderr_path = tempdir.path().join("stderr.log");
+
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--stdout=inherit")
+        .arg(format!("--stderr=file:{}", stderr_path.to_str().unwrap()))
+        .arg("echo visible-output; echo error-output >&2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("visible-output"))
+        .stderr(predicate::str::contains("error-output").not());
+
+    assert_eq!(
+        std::fs::read_to_string(stderr_path).unwrap(),
+        "error-output\n"
+    );
+
+    hyperfine_debug()
+        .arg("--stderr=err.log")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown output policy 'err.log'"));
+}
+
+#[cfg(unix)]
+#[test]
+fn redirects_stdout_and_stderr_to_the_same_file() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let output_path = tempdir.path().join("output.log");
+    let output_path = output_path.to_str().unwrap();
+
+    hyperfine()
+        .arg("--runs=1")
+        .arg(format!("--stdout=file:{output_path}"))
+        .arg(format!("--stderr=file:{output_path}"))
+        .arg("echo stdout-output; echo stderr-output >&2")
+        .assert()
+        .success();
+
+    assert_eq!(
+        std::fs::read_to_string(output_path).unwrap(),
+        "stdout-output\nstderr-output\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn writes_output_of_each_run_to_separate_files() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let template = tempdir
+        .path()
+        .join("{command_index}/{iteration}.{stream}")
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    hyperfine()
+        .arg("--runs=2")
+        .arg("--warmup=1")
+        .arg("--output")
+        .arg(template)
+        .arg("echo out-$HYPERFINE_ITERATION; echo err >&2")
+        .assert()
+        .success();
+
+    let read = |name: &str| std::fs::read_to_string(tempdir.path().join("1").join(name)).unwrap();
+    assert_eq!(read("warmup-0.stdout"), "out-warmup-0\n");
+    assert_eq!(read("0.stdout"), "out-0\n");
+    assert_eq!(read("1.stdout"), "out-1\n");
+    assert_eq!(read("1.stderr"), "err\n");
+}
+
+#[test]
+fn rejects_parameter_names_of_output_template_placeholders() {
+    hyperfine_debug()
+        .arg("--output=logs/{iteration}.{stream}")
+        .arg("--parameter-list")
+        .arg("stream")
+        .arg("1,2")
+        .arg("sleep {stream}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The parameter name 'stream' can not be used in combination with an '--output' path template",
+        ));
+}
+
+#[cfg(unix)]
+#[test]
+fn checks_command_output() {
+    const HELLO_SHA256: &str = "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03";
+
+    hyperfine()
+        .arg("--runs=2")
+        .arg("--expect-output-hash")
+        .arg(HELLO_SHA256)
+        .arg("echo hello")
+        .assert()
+        .success();
+
+    hyperfine()
+        .arg("--runs=2")
+        .arg("--expect-output-hash")
+        .arg(HELLO_SHA256)
+        .arg("echo hello")
+        .arg("echo bye")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The output of 'echo bye' in the output check run",
+        ))
+        .stderr(predicate::str::contains("differs from the expected output"));
+
+    hyperfine()
+        .arg("--runs=2")
+        .arg("--expect-output=example_input_file.txt")
+        .arg("cat example_input_file.txt")
+        .assert()
+        .success();
+
+    hyperfine()
+        .arg("--runs=3")
+        .arg("--check-output=all")
+        .arg("--output-mismatch=warn")
+        .arg("echo $HYPERFINE_ITERATION")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "in benchmark iteration 1 (SHA-256: 4355a46b19d348dc2f57c046f8ef63d4538ebb936000f3c9ee954a27460dd865) \
+             differs from the output of the first run",
+        ));
+}
+
+#[cfg(unix)]
+#[test]
+fn checks_that_commands_produce_identical_output() {
+    hyperfine()
+        .arg("--runs=2")
+        .arg("--expect-identical-output")
+        .arg("echo hello")
+        .arg("printf 'hello\\n'")
+        .assert()
+        .success();
+
+    hyperfine()
+        .arg("--runs=2")
+        .arg("--expect-identical-output")
+        .arg("echo hello")
+        .arg("echo bye")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The output of 'echo bye' (SHA-256: ",
+        ))
+        .stderr(predicate::str::contains(
+            "differs from the output of 'echo hello'",
+        ));
+}
+
+#[test]
+fn fails_for_invalid_output_checks() {
+    hyperfine_debug()
+        .arg("--expect-output-hash=abc")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid SHA-256 hash 'abc'"));
+
+    hyperfine_debug()
+        .arg("--expect-output=example_non_existent_file.txt")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The file 'example_non_existent_file.txt' specified as '--expect-output' could not be read",
+        ));
+
+    hyperfine_debug()
+        .arg("--show-output")
+        .arg("--expect-identical-output")
+        .arg("sleep 1")
+        .arg("sleep 2")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The output of the commands needs to be captured in order to check it",
+        ));
+}
+
+#[test]
+fn returns_mean_time_in_correct_unit() {
+    hyperfine_debug()
+        .arg("sleep 1.234")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Time (mean ± σ):      1.234 s ±"));
+
+    hyperfine_debug()
+        .arg("sleep 0.123")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Time (mean ± σ):     123.0 ms ±"));
+
+    hyperfine_debug()
+        .arg("--time-unit=millisecond")
+        .arg("sleep 1.234")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Time (mean ± σ):     1234.0 ms ±"));
+
+    hyperfine_debug()
+        .arg("--time-unit=microsecond")
+        .arg("sleep 1.234")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Time (mean ± σ):     1234000.0 µs ±",
+        ));
+}
+
+#[test]
+fn shows_histogram_and_sparkline() {
+    hyperfine_debug()
+        .arg("--histogram")
+        .arg("--style=full")
+        .arg("--runs=3")
+        .arg("sleep 0.1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Histogram:").and(predicate::str::contains("▄▄▄")));
+
+    hyperfine_debug()
+        .arg("--histogram")
+        .arg("--style=basic")
+        .arg("--runs=3")
+        .arg("sleep 0.1")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Histogram:").and(predicate::str::contains("Run order:  ===")),
+        );
+
+    hyperfine_debug()
+        .arg("sleep 0.1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Histogram:").not());
+}
+
+#[test]
+fn fails_for_invalid_percentiles_and_export_columns() {
+    hyperfine_debug()
+        .arg("--percentiles=50,150")
+        .arg("sleep 0.1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid percentile '150'"));
+
+    hyperfine_debug()
+        .arg("--export-columns=command,average")
+        .arg("sleep 0.1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown export column 'average'"));
+}
+
+#[test]
+fn exports_selected_percentile_columns() {
+    hyperfine_debug()
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg("--export-columns=command,p90")
+        .arg("--export-markdown=-")
+        .arg("sleep 0.1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| Command | p90 [ms] |\n|:---|---:|\n| `sleep 0.1` | 100.0 |",
+        ));
+}
+
+#[test]
+fn shows_selected_columns_in_terminal() {
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--export-columns=command,median,parameter_t,relative")
+        .arg("--parameter-list")
+        .arg("t")
+        .arg("1,2")
+        .arg("sleep 0.{t}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "  Command    Median [ms]  t     Relative\n  \
+               sleep 0.1        100.0  1         1.00\n  \
+               sleep 0.2        200.0  2  2.00 ± 0.00\n",
+        ));
+
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("sleep 0.1")
+        .arg("sleep 0.2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Median").not());
+}
+
+#[test]
+fn exports_details_of_individual_runs() {
+    let output = hyperfine_debug()
+        .arg("--style=none")
+        .arg("--warmup=1")
+        .arg("--runs=2")
+        .arg("--export-json=-")
+        .arg("sleep 0.1")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let result = &json["results"][0];
+    assert_eq!(result["times_user"].as_array().unwrap().len(), 2);
+    assert_eq!(result["times_system"].as_array().unwrap().len(), 2);
+
+    let runs = result["runs"].as_array().unwrap();
+    let kinds: Vec<_> = runs
+        .iter()
+        .map(|run| {
+            (
+                run["kind"].as_str().unwrap(),
+                run["iteration"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(kinds, [("warmup", 0), ("benchmark", 0), ("benchmark", 1)]);
+    assert!(runs
+        .windows(2)
+        .all(|w| w[0]["start"].as_f64() <= w[1]["start"].as_f64()));
+    assert!(runs[2].get("time_real").is_none());
+}
+
+#[test]
+fn exports_phase_times() {
+    let output = hyperfine_debug()
+        .arg("--style=none")
+        .arg("--warmup=1")
+        .arg("--runs=2")
+        .arg("--setup=sleep 0.5")
+        .arg("--prepare=sleep 0.2")
+        .arg("--conclude=sleep 0.3")
+        .arg("--cleanup=sleep 0.4")
+        .arg("--export-phase-times")
+        .arg("--export-json=-")
+        .arg("sleep 0.1")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let phase_times = &json["results"][0]["phase_times"];
+    assert_eq!(phase_times["warmup"], serde_json::json!([0.1]));
+    assert_eq!(phase_times["prepare"], serde_json::json!([0.2, 0.2, 0.2]));
+    assert_eq!(phase_times["conclude"], serde_json::json!([0.3, 0.3, 0.3]));
+    assert_eq!(phase_times["setup"], 0.5);
+    assert_eq!(phase_times["cleanup"], 0.4);
+
+    hyperfine_debug()
+        .arg("--style=none")
+        .arg("--export-json=-")
+        .arg("sleep 0.1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("phase_times").not());
+}
+
+#[test]
+fn streams_benchmark_events_as_ndjson() {
+    let output = hyperfine_debug()
+        .arg("--warmup=1")
+        .arg("--runs=2")
+        .arg("--export-ndjson=-")
+        .arg("sleep 0.1")
+        .arg("sleep 0.2")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let events: Vec<serde_json::Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let kinds: Vec<&str> = events
+        .iter()
+        .map(|event| event["event"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            "session_start",
+            "benchmark_start",
+            "run",
+            "run",
+            "run",
+            "benchmark_end",
+            "benchmark_start",
+            "run",
+            "run",
+            "run",
+            "benchmark_end",
+            "session_end",
+        ]
+    );
+
+    assert_eq!(
+        events[0]["commands"],
+        serde_json::json!(["sleep 0.1", "sleep 0.2"])
+    );
+    assert_eq!(events[2]["kind"], "warmup");
+    assert_eq!(events[4]["benchmark"], 0);
+    assert_eq!(events[4]["iteration"], 1);
+    assert_eq!(events[4]["time_real"], 0.1);
+    assert_eq!(events[4]["exit_code"], 0);
+    assert_eq!(events[10]["result"]["mean"], 0.2);
+
+    let times: Vec<f64> = events
+        .iter()
+        .map(|event| event["time"].as_f64().unwrap())
+        .collect();
+    assert!(times.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[test]
+fn streams_session_end_event_if_a_benchmark_fails() {
+    let output = hyperfine()
+        .arg("--runs=1")
+        .arg("--export-ndjson=-")
+        .arg("some-nonexisting-program-b5d9574198b7e4b12a71fa4747c0a577")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let last_event: serde_json::Value =
+        serde_json::from_str(String::from_utf8(output).unwrap().lines().last().unwrap()).unwrap();
+    assert_eq!(last_event["event"], "session_end");
+    assert!(last_event["error"]
+        .as_str()
+        .unwrap()
+        .contains("Command terminated with non-zero exit code"));
+}
+
+#[test]
+fn rejects_output_style_with_ndjson_events_on_stdout() {
+    hyperfine_debug()
+        .arg("--style=basic")
+        .arg("--export-ndjson=-")
+        .arg("sleep 0.1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "can not be combined with '--style basic'",
+        ));
+}
+
+#[test]
+fn converts_exported_json_results() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let json_path = tempdir.path().join("results.json");
+    let json_path = json_path.to_str().unwrap();
+
+    hyperfine_debug()
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg(format!("--export-json={json_path}"))
+        .arg("sleep 0.1")
+        .arg("sleep 0.3")
+        .assert()
+        .success();
+
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(json_path).unwrap()).unwrap();
+    assert_eq!(json["schema_version"], 1);
+
+    hyperfine()
+        .arg("--from-json")
+        .arg(json_path)
+        .arg("--export-markdown=-")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "3.00 ± 0.00 times faster than sleep 0.3",
+        ))
+        .stdout(predicate::str::contains(
+            "| `sleep 0.3` | 300.0 ± 0.0 | 300.0 | 300.0 | 3.00 ± 0.00 |",
+        ));
+
+    hyperfine()
+        .arg("--from-json")
+        .arg(json_path)
+        .arg("echo")
+        .assert()
+        .failure();
+
+    hyperfine()
+        .arg("--from-json")
+        .arg("example_input_file.txt")
         .assert()
         .failure()
         .stderr(predicate::str::contains(
-            "The file 'example_non_existent_file.txt' specified as '--input' does not exist",
+            "'example_input_file.txt' does not contain valid hyperfine results",
         ));
 }
 
 #[test]
-fn returns_mean_time_in_correct_unit() {
+fn exports_html_report() {
     hyperfine_debug()
-        .arg("sleep 1.234")
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg("--export-html=-")
+        .arg("--statistic=min")
+        .arg("sleep 0.1")
+        .arg("sleep 0.2")
         .assert()
         .success()
-        .stdout(predicate::str::contains("Time (mean ± σ):      1.234 s ±"));
+        .stdout(
+            predicate::str::contains("<!DOCTYPE html>")
+                .and(predicate::str::contains(
+                    "<title>hyperfine benchmark report</title>",
+                ))
+                .and(predicate::str::contains("<h2>Relative speed (by min)</h2>"))
+                .and(predicate::str::contains("200.0 ms (2.00×)"))
+                .and(predicate::str::contains("<h3><code>sleep 0.2</code></h3>"))
+                .and(predicate::str::contains("</html>")),
+        );
+}
 
+#[test]
+fn exports_junit_report() {
     hyperfine_debug()
-        .arg("sleep 0.123")
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg("--export-junit=-")
+        .arg("--junit-threshold=2")
+        .arg("sleep 0.1")
+        .arg("sleep 0.15")
+        .arg("sleep 0.3")
         .assert()
         .success()
-        .stdout(predicate::str::contains("Time (mean ± σ):     123.0 ms ±"));
+        .stdout(
+            predicate::str::contains("<testsuites name=\"hyperfine\" tests=\"3\" failures=\"1\"")
+                .and(predicate::str::contains(
+                    "<testcase name=\"sleep 0.15\" classname=\"hyperfine\" time=\"0.150000\">",
+                ))
+                .and(predicate::str::contains(
+                    "<failure message=\"Command is 3.00 times slower than &#39;sleep 0.1&#39; \
+                     (threshold: 2.00)\"",
+                )),
+        );
 
     hyperfine_debug()
-        .arg("--time-unit=millisecond")
-        .arg("sleep 1.234")
+        .arg("--junit-threshold=0.9")
+        .arg("--export-junit=-")
+        .arg("sleep 0.1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The factor for '--junit-threshold' must be at least 1",
+        ));
+}
+
+#[test]
+fn exports_openmetrics() {
+    hyperfine_debug()
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg("--export-openmetrics=-")
+        .arg("--parameter-list")
+        .arg("delay")
+        .arg("0.1,0.2")
+        .arg("sleep {delay}")
         .assert()
         .success()
-        .stdout(predicate::str::contains("Time (mean ± σ):     1234.0 ms ±"));
+        .stdout(
+            predicate::str::contains("# TYPE hyperfine_mean_seconds gauge\n")
+                .and(predicate::str::contains(
+                    "hyperfine_mean_seconds{command=\"sleep 0.1\",delay=\"0.1\"} 0.1\n",
+                ))
+                .and(predicate::str::contains(
+                    "hyperfine_max_seconds{command=\"sleep 0.2\",delay=\"0.2\"} 0.2\n",
+                ))
+                .and(predicate::str::contains("} 0\n# EOF\n")),
+        );
+}
+
+#[test]
+fn exports_continuous_benchmarking_formats() {
+    let output = hyperfine_debug()
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg("--export-github-benchmark=-")
+        .arg("--command-name=sleep")
+        .arg("--parameter-list")
+        .arg("delay")
+        .arg("0.1,0.2")
+        .arg("sleep {delay}")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let entries: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(entries[0]["name"], "sleep (delay = 0.1)");
+    assert_eq!(entries[1]["name"], "sleep (delay = 0.2)");
+    assert_eq!(entries[1]["unit"], "ms");
+    assert_eq!(entries[1]["value"], 200.0);
+    assert_eq!(entries[1]["range"], "± 0.0");
+
+    let output = hyperfine_debug()
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg("--export-bencher=-")
+        .arg("sleep 0.5")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let benchmarks: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        benchmarks,
+        serde_json::json!({
+            "sleep 0.5": {
+                "latency": {
+                    "value": 5e8,
+                    "lower_value": 5e8,
+                    "upper_value": 5e8,
+                },
+            },
+        })
+    );
+}
 
+#[test]
+fn exports_latex_and_rst_tables() {
     hyperfine_debug()
-        .arg("--time-unit=microsecond")
-        .arg("sleep 1.234")
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg("--export-latex=-")
+        .arg("--command-name=fast")
+        .arg("--command-name=a_b & c")
+        .arg("sleep 0.1")
+        .arg("sleep 0.2")
         .assert()
         .success()
         .stdout(predicate::str::contains(
-            "Time (mean ± σ):     1234000.0 µs ±",
+            "\\begin{tabular}{lrrrr}\n\
+             \\toprule\n\
+             Command & Mean [ms] & Min [ms] & Max [ms] & Relative \\\\\n\
+             \\midrule\n\
+             \\texttt{fast} & 100.0 $\\pm$ 0.0 & 100.0 & 100.0 & 1.00 \\\\\n\
+             \\texttt{a\\_b \\& c} & 200.0 $\\pm$ 0.0 & 200.0 & 200.0 & 2.00 $\\pm$ 0.00 \\\\\n\
+             \\bottomrule\n\
+             \\end{tabular}\n",
+        ));
+
+    hyperfine_debug()
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg("--export-rst=-")
+        .arg("--export-columns=command,mean")
+        .arg("--command-name=fast")
+        .arg("--command-name=a_b | c")
+        .arg("sleep 0.1")
+        .arg("sleep 0.2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "+----------------------+-------------+\n\
+             | Command              |   Mean [ms] |\n\
+             +======================+=============+\n\
+             | :literal:`fast`      | 100.0 ± 0.0 |\n\
+             +----------------------+-------------+\n\
+             | :literal:`a\\_b \\| c` | 200.0 ± 0.0 |\n\
+             +----------------------+-------------+\n",
         ));
 }
 
+#[test]
+fn exports_system_metadata() {
+    hyperfine_debug()
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg("--export-json=-")
+        .arg("sleep 0.1")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("\"metadata\": {")
+                .and(predicate::str::contains("\"hyperfine_version\""))
+                .and(predicate::str::contains("\"start_time\""))
+                .and(predicate::str::contains("\"end_time\"")),
+        );
+
+    hyperfine_debug()
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg("--export-markdown=-")
+        .arg("sleep 0.1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| Property | Value |").not());
+
+    hyperfine_debug()
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg("--export-metadata")
+        .arg("--export-markdown=-")
+        .arg("sleep 0.1")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("| Property | Value |\n|:---|:---|\n| hyperfine version |")
+                .and(predicate::str::contains("| Command | Mean [ms] |")),
+        );
+}
+
+#[test]
+fn reports_shell_spawning_time_calibration() {
+    hyperfine()
+        .arg("--runs=2")
+        .arg("--style=basic")
+        .arg("--recalibrate")
+        .arg("--show-calibration")
+        .arg("--export-json=-")
+        .arg("echo a")
+        .arg("echo b")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Shell spawning time (median of 50 runs):").count(2))
+        .stdout(predicate::str::contains("\"calibration\": {").count(2))
+        .stdout(predicate::str::contains("\"uncertainty\":"));
+
+    hyperfine()
+        .arg("--runs=2")
+        .arg("--style=basic")
+        .arg("--export-json=-")
+        .arg("echo a")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Shell spawning time").not())
+        .stdout(predicate::str::contains("\"calibration\": {").count(1));
+
+    hyperfine()
+        .arg("--runs=2")
+        .arg("--shell=none")
+        .arg("--export-json=-")
+        .arg("echo a")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Shell spawning time").not())
+        .stdout(predicate::str::contains("\"calibration\"").not());
+}
+
 #[test]
 fn performs_ten_runs_for_slow_commands() {
     hyperfine_debug()
@@ -705,6 +1546,240 @@ fn speed_comparison_sort_order() {
         ));
 }
 
+#[test]
+fn speed_comparison_uses_selected_statistic() {
+    hyperfine_debug()
+        .arg("--statistic=median")
+        .arg("sleep 2")
+        .arg("sleep 1")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Summary (by median)").and(predicate::str::contains(
+                "sleep 1 ran\n    2.00 times faster than sleep 2",
+            )),
+        );
+
+    hyperfine_debug()
+        .arg("--style=none")
+        .arg("--statistic=p75")
+        .arg("--percentiles=50")
+        .arg("--export-json=-")
+        .arg("sleep 1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"p75\": 1.0"));
+
+    hyperfine_debug()
+        .arg("--statistic=max")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown statistic 'max'"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn applies_niceness_and_cpu_affinity_to_benchmarked_commands() {
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--show-output")
+        .arg("--nice=7")
+        .arg("--cpu-affinity=0")
+        .arg("-N")
+        .arg("nice")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("7\n"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn can_evict_files_from_page_cache() {
+    hyperfine()
+        .arg("--runs=2")
+        .arg("--cold-cache-file=example_input_file.txt")
+        .arg("-N")
+        .arg("cat example_input_file.txt")
+        .assert()
+        .success();
+}
+
+#[cfg(unix)]
+#[test]
+fn sets_environment_variables_and_working_directory() {
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--show-output")
+        .arg("--env=GREETING=hello NAME='hyper fine'")
+        .arg("--cwd=..")
+        .arg("echo $GREETING $NAME; ls")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("hello hyper fine")
+                .and(predicate::str::contains("Cargo.toml")),
+        );
+}
+
+#[cfg(unix)]
+#[test]
+fn sets_parameterized_environment_variables_for_all_commands() {
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--show-output")
+        .arg("--parameter-list")
+        .arg("value")
+        .arg("one,two")
+        .arg("--env=VALUE=first-{value}")
+        .arg("echo $VALUE")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("first-one").and(predicate::str::contains("first-two")));
+
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--show-output")
+        .arg("--env=FIRST=first")
+        .arg("--env=SECOND=second")
+        .arg("--prepare=echo prepare $FIRST $SECOND")
+        .arg("echo 1 $FIRST $SECOND")
+        .arg("echo 2 $FIRST $SECOND")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("prepare first second")
+                .and(predicate::str::contains("1 first second"))
+                .and(predicate::str::contains("2 first second")),
+        );
+}
+
+#[cfg(unix)]
+#[test]
+fn passes_parameters_as_environment_variables() {
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--show-output")
+        .arg("--parameter-scan")
+        .arg("NUM_THREADS")
+        .arg("1")
+        .arg("2")
+        .arg("--parameter-env=NUM_THREADS")
+        .arg("--export-csv=-")
+        .arg("echo threads=$NUM_THREADS")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("threads=1")
+                .and(predicate::str::contains("threads=2"))
+                .and(predicate::str::contains(
+                    "Benchmark 2: echo threads=$NUM_THREADS (NUM_THREADS = 2)",
+                ))
+                .and(predicate::str::contains("parameter_NUM_THREADS")),
+        );
+}
+
+#[test]
+fn fails_for_invalid_environment_options() {
+    hyperfine_debug()
+        .arg("--env=RUST_LOG")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid environment variable assignment 'RUST_LOG'",
+        ));
+
+    hyperfine_debug()
+        .arg("--cwd=a")
+        .arg("--cwd=b")
+        .arg("--cwd=c")
+        .arg("sleep 1")
+        .arg("sleep 2")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The '--cwd' option has to be provided just once or N times",
+        ));
+
+    hyperfine_debug()
+        .arg("--parameter-list")
+        .arg("threads")
+        .arg("1,2")
+        .arg("--parameter-env=THREADS")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unknown parameter 'THREADS' in '--parameter-env'",
+        ));
+}
+
+#[test]
+fn fails_for_missing_cold_cache_file() {
+    hyperfine_debug()
+        .arg("--cold-cache-file=does-not-exist.txt")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The file 'does-not-exist.txt' specified as '--cold-cache-file' does not exist",
+        ));
+}
+
+#[test]
+fn fails_for_invalid_scheduling_options() {
+    hyperfine_debug()
+        .arg("--nice=20")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid niceness 20"));
+
+    hyperfine_debug()
+        .arg("--sched-fifo=100")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid real-time priority 100"));
+
+    hyperfine_debug()
+        .arg("--cpu-affinity=3-1")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid CPU list '3-1'"));
+}
+
+#[test]
+fn skips_environment_checks_in_debug_mode() {
+    hyperfine_debug()
+        .arg("--strict-environment")
+        .arg("sleep 1")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("not in a suitable state").not());
+}
+
+#[test]
+fn accepts_outlier_policies() {
+    for policy in ["keep", "remove", "winsorize"] {
+        hyperfine_debug()
+            .arg(format!("--outliers={policy}"))
+            .arg("sleep 1")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("10 runs\n"));
+    }
+
+    hyperfine_debug()
+        .arg("--outliers=drop")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'drop'"));
+}
+
 #[cfg(windows)]
 #[test]
 fn windows_quote_args() {